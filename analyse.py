@@ -4,7 +4,10 @@ import subprocess
 
 PROGRAM = "./target/release/cache_algos"
 CACHE_SIZE = 2
-ALGOS = ["BELADY", "FIFO", "LRU", "LFU", "RR", "MRU", "SLRU"]
+ALGOS = ["BELADY", "FIFO", "LRU", "LFU", "RR", "MRU", "SLRU", "2Q", "ARC", "CAR", "CART", "MQ", "LIRS",
+         "CLOCK", "GCLOCK", "NRU", "CLOCKPRO", "SIEVE", "S3FIFO",
+         "TINYLFU", "WTINYLFU", "LRU2", "LFUDA", "GDSF", "WLFU", "LRFU",
+         "LECAR"]
 FILE = "/usr/bin/clang-3.8"
 OUTPUT_NAME = "algos.gnuplot"
 
@@ -0,0 +1,238 @@
+//! Implementation of ARC (Adaptive Replacement Cache) cache algorithm.
+//!
+//! Constantly balances between LRU and LFU, to improve the combined result. ARC keeps track of both
+//! frequently used and recently used pages plus a recent eviction history for both. Elements, which
+//! were requested once, are kept in the list T1, elements, which were requested at least twice, are
+//! kept in the list T2. Elements, discarded from T1 and T2, are remembered (without data) in the
+//! lists B1 and B2.
+//!
+//! The target size of T1 `p` is adapted on every request of the element from B1 or B2: hit in B1
+//! means that T1 is too small, so `p` is increased, hit in B2 means that T2 is too small, so `p` is
+//! decreased.
+
+use slog;
+
+use std::cmp;
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+// List, which has the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Queue {
+    T1,
+    T2,
+    B1,
+    B2,
+}
+
+/// Implementation cache, based on a ARC algorithm.
+///
+/// Elements of B1 and B2 are not counted as elements of the cache. Every hit and miss is logged
+/// with the current value of `p`.
+pub struct ARCCache<K> {
+    t1: List<K>,
+    t2: List<K>,
+    b1: List<K>,
+    b2: List<K>,
+    map: HashMap<K, (Queue, usize)>,
+    p: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> ARCCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created ARC cache with size: {}", size);
+        ARCCache {
+            t1: List::with_capacity(size),
+            t2: List::with_capacity(size),
+            b1: List::with_capacity(size),
+            b2: List::with_capacity(size),
+            map: HashMap::with_capacity(size * 2),
+            p: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Current target size of T1.
+    pub fn p(&self) -> usize {
+        self.p
+    }
+
+    // List by its name.
+    fn list(&mut self, queue: Queue) -> &mut List<K> {
+        match queue {
+            Queue::T1 => &mut self.t1,
+            Queue::T2 => &mut self.t2,
+            Queue::B1 => &mut self.b1,
+            Queue::B2 => &mut self.b2,
+        }
+    }
+
+    // Move the least recently used element of the list to the front of other list.
+    fn demote(&mut self, from: Queue, to: Queue) -> Option<K> {
+        self.list(from).pop_back().map(|key| {
+            let index = self.list(to).push_front(key.clone());
+            self.map.insert(key.clone(), (to, index));
+            key
+        })
+    }
+
+    // Forget the least recently used element of the list.
+    fn forget(&mut self, queue: Queue) -> Option<K> {
+        self.list(queue).pop_back().map(|key| {
+            self.map.remove(&key);
+            key
+        })
+    }
+
+    // Discard element from T1 or T2 to the ghost list, if the cache is full.
+    fn replace(&mut self, in_b2: bool) -> Option<K> {
+        if self.t1.len() + self.t2.len() < self.size {
+            return None;
+        }
+        let t1 = self.t1.len();
+        if t1 > 0 && (t1 > self.p || (in_b2 && t1 == self.p) || self.t2.len() == 0) {
+            self.demote(Queue::T1, Queue::B1)
+        } else {
+            self.demote(Queue::T2, Queue::B2)
+        }
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for ARCCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "t1" => format!("{:?}", self.t1),
+                   "t2" => format!("{:?}", self.t2),
+                   "b1" => format!("{:?}", self.b1),
+                   "b2" => format!("{:?}", self.b2),
+                   "p" => self.p,
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "t1" => format!("{:?}", self.t1),
+                   "t2" => format!("{:?}", self.t2),
+                   "b1" => format!("{:?}", self.b1),
+                   "b2" => format!("{:?}", self.b2),
+                   "p" => self.p,
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.t1.clear();
+        self.t2.clear();
+        self.b1.clear();
+        self.b2.clear();
+        self.map.clear();
+        self.p = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+}
+
+impl<K: Key> Policy<K> for ARCCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        match self.map.get(key) {
+            Some(&(Queue::T1, _)) |
+            Some(&(Queue::T2, _)) => true,
+            _ => false,
+        }
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        match self.map.get(key).cloned() {
+            Some((Queue::T1, index)) => {
+                let key = self.t1.remove(index);
+                let index = self.t2.push_front(key.clone());
+                self.map.insert(key, (Queue::T2, index));
+            }
+            Some((Queue::T2, index)) => self.t2.move_to_front(index),
+            _ => {}
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let (b1, b2) = (self.b1.len(), self.b2.len());
+        let (evicted, queue) = match self.map.get(&key).cloned() {
+            Some((Queue::B1, index)) => {
+                self.p = cmp::min(self.size, self.p + cmp::max(1, b2 / b1));
+                debug!(self.logger, "hit in B1"; "p" => self.p);
+                self.b1.remove(index);
+                (self.replace(false), Queue::T2)
+            }
+            Some((Queue::B2, index)) => {
+                self.p = self.p.saturating_sub(cmp::max(1, b1 / b2));
+                debug!(self.logger, "hit in B2"; "p" => self.p);
+                self.b2.remove(index);
+                (self.replace(true), Queue::T2)
+            }
+            _ => {
+                let evicted = if self.t1.len() + b1 >= self.size {
+                    if self.t1.len() < self.size {
+                        self.forget(Queue::B1);
+                        self.replace(false)
+                    } else {
+                        self.forget(Queue::T1)
+                    }
+                } else {
+                    if self.t1.len() + self.t2.len() + b1 + b2 >= 2 * self.size {
+                        self.forget(Queue::B2);
+                    }
+                    self.replace(false)
+                };
+                (evicted, Queue::T1)
+            }
+        };
+        let index = self.list(queue).push_front(key.clone());
+        self.map.insert(key, (queue, index));
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        match self.map.get(key).cloned() {
+            Some((Queue::T1, index)) => {
+                self.t1.remove(index);
+            }
+            Some((Queue::T2, index)) => {
+                self.t2.remove(index);
+            }
+            _ => return false,
+        }
+        self.map.remove(key);
+        true
+    }
+
+    // Element from B1 or B2 changes `p` before discarding, so the element is told for new element.
+    fn victim(&self) -> Option<K> {
+        if self.t1.len() + self.t2.len() < self.size {
+            return None;
+        }
+        let t1 = self.t1.len();
+        let list = if t1 > 0 && (t1 > self.p || self.t2.len() == 0) {
+            &self.t1
+        } else {
+            &self.t2
+        };
+        list.back().map(|index| list.get(index).clone())
+    }
+}
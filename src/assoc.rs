@@ -0,0 +1,207 @@
+//! Implementation of set-associative cache, which is used to model CPU caches.
+//!
+//! Cache algorithms of this crate model fully-associative caches, where any element may be put into
+//! any line of the cache. CPU caches are set-associative: the cache of `S` bytes with lines of `L`
+//! bytes and associativity `W` (ways) has `S / (L * W)` sets of `W` lines. The address of the byte
+//! is split into the offset in the line (the lowest bits), the index of the set and the tag. The line
+//! may be put only into its set, so lines of different sets never discard each other, and every set
+//! is managed by its own cache algorithm (usually LRU, FIFO, RR or PLRU).
+//!
+//! Lines, which addresses differ by a multiple of the count of sets, fall into the same set and may
+//! discard each other, even if the cache has free lines (conflict misses). XOR indexing mixes the
+//! index with the lowest bits of the tag, so strided accesses are spread over all sets.
+
+use slog;
+use rand::{self, Rng, SeedableRng, StdRng};
+
+use std::marker::PhantomData;
+
+use {Algos, CacheAlgorithm};
+
+/// Elements, which are addresses of bytes in memory.
+pub trait Address {
+    /// Address of the byte.
+    fn address(&self) -> u64;
+}
+
+macro_rules! impl_address {
+    ($($t:ty),*) => {
+        $(impl Address for $t {
+            fn address(&self) -> u64 {
+                *self as u64
+            }
+        })*
+    }
+}
+
+impl_address!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Mapping of lines to sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indexing {
+    /// Index of the set is the line address modulo count of sets (index bits of the address).
+    Modulo,
+    /// Index bits of the address are XORed with the lowest bits of the tag.
+    XOR,
+}
+
+/// Implementation of set-associative cache.
+///
+/// Every set is created by `init_with_seed` for the algorithm with size equal to the associativity,
+/// sets keep line addresses (the address divided by the line size). By default sets are chosen by
+/// `Indexing::Modulo`.
+///
+/// Sets are independent, so `run` splits requests by sets and runs every set on its own requests,
+/// so Bélády's algorithm works per set too (it sees only the future of its set).
+pub struct SetAssociativeCache<A> {
+    sets: Vec<Box<dyn CacheAlgorithm<u64>>>,
+    indexing: Indexing,
+    line_size: u64,
+    index_bits: u32,
+    size: usize,
+    ways: usize,
+    logger: slog::Logger,
+    marker: PhantomData<A>,
+}
+
+impl<A: Address> SetAssociativeCache<A> {
+    /// Create new cache with total size and the line size in bytes, the associativity (count of
+    /// lines in every set) and random seed.
+    ///
+    /// Panics, if sizes are zero or the cache can't be divided into sets of whole lines.
+    pub fn new(algo: Algos,
+               size: usize,
+               line_size: usize,
+               ways: usize,
+               logger: Option<slog::Logger>)
+               -> Self {
+        SetAssociativeCache::with_seed(algo, size, line_size, ways, rand::random(), logger)
+    }
+
+    /// Create new cache with total size and the line size in bytes, the associativity (count of
+    /// lines in every set) and the seed of random, seeds of sets are generated from the seed.
+    ///
+    /// Panics, if sizes are zero or the cache can't be divided into sets of whole lines.
+    pub fn with_seed(algo: Algos,
+                     size: usize,
+                     line_size: usize,
+                     ways: usize,
+                     seed: usize,
+                     logger: Option<slog::Logger>)
+                     -> Self {
+        assert!(line_size > 0 && ways > 0, "Line size and associativity must not be zero");
+        assert!(size > 0 && size % (line_size * ways) == 0,
+                "Size of cache must be a multiple of line size and associativity");
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        let count_sets = size / (line_size * ways);
+        debug!(logger, "Created {}-way set-associative {} cache with size: {}", ways, algo, size);
+        debug!(logger, "With line size: {}", line_size);
+        debug!(logger, "With count of sets: {}", count_sets);
+        let mut seeds = StdRng::from_seed(&[seed][..]);
+        let sets = (0..count_sets)
+            .map(|set| {
+                ::init_with_seed(algo, ways, seeds.gen(), Some(logger.new(o!("set" => set))))
+            })
+            .collect();
+        SetAssociativeCache {
+            sets: sets,
+            indexing: Indexing::Modulo,
+            line_size: line_size as u64,
+            index_bits: count_sets.trailing_zeros(),
+            size: size,
+            ways: ways,
+            logger: logger,
+            marker: PhantomData,
+        }
+    }
+
+    /// Set mapping of lines to sets.
+    ///
+    /// Panics, if XOR indexing is set and count of sets is not a power of two.
+    pub fn with_indexing(mut self, indexing: Indexing) -> Self {
+        assert!(indexing == Indexing::Modulo || self.sets.len().is_power_of_two(),
+                "XOR indexing needs count of sets to be a power of two");
+        self.indexing = indexing;
+        debug!(self.logger, format!("With indexing: {:?}", self.indexing));
+        self
+    }
+
+    /// Count of sets.
+    pub fn sets(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Associativity (count of lines in every set).
+    pub fn ways(&self) -> usize {
+        self.ways
+    }
+
+    /// Size of the line in bytes.
+    pub fn line_size(&self) -> usize {
+        self.line_size as usize
+    }
+
+    /// Total size of the cache in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Return address of the line and index of its set for the address of the byte.
+    pub fn locate(&self, address: &A) -> (u64, usize) {
+        let line = address.address() / self.line_size;
+        let count = self.sets.len() as u64;
+        let set = match self.indexing {
+            Indexing::Modulo => line % count,
+            Indexing::XOR => (line ^ (line >> self.index_bits)) & (count - 1),
+        };
+        (line, set as usize)
+    }
+
+    /// Count of lines in the set.
+    pub fn set_len(&self, set: usize) -> usize {
+        self.sets[set].len()
+    }
+}
+
+impl<A: Address> CacheAlgorithm<A> for SetAssociativeCache<A> {
+    fn access(&mut self, val: &A) -> bool {
+        let (line, set) = self.locate(val);
+        if self.sets[set].access(&line) {
+            debug!(self.logger, "hit"; "set" => set, "hit" => val.address());
+            true
+        } else {
+            debug!(self.logger, "miss"; "set" => set, "hit" => val.address());
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        for set in self.sets.iter_mut() {
+            set.reset();
+        }
+    }
+
+    /// Count of lines in the cache.
+    fn capacity(&self) -> usize {
+        self.sets.len() * self.ways
+    }
+
+    fn len(&self) -> usize {
+        self.sets.iter().map(|set| set.len()).sum()
+    }
+
+    fn run(&mut self, ram: &Vec<Vec<A>>) -> (i32, i32) {
+        let mut requests = vec![Vec::new(); self.sets.len()];
+        for elem in ram.iter().flat_map(|batch| batch.iter()) {
+            let (line, set) = self.locate(elem);
+            requests[set].push(line);
+        }
+        let mut statistic = (0, 0);
+        for (set, requests) in self.sets.iter_mut().zip(requests.into_iter()) {
+            let result = set.run(&vec![requests]);
+            statistic.0 += result.0;
+            statistic.1 += result.1;
+        }
+        statistic
+    }
+}
@@ -9,84 +9,170 @@
 
 use slog;
 
-use std::collections::VecDeque;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::usize;
+
+use {CacheAlgorithm, Key};
+
+// Position of the request, which will never come.
+const NEVER: usize = usize::MAX;
+
+// Element of the cache with position of its next request, ordered by the position.
+struct NextUse<K> {
+    position: usize,
+    key: K,
+}
+
+impl<K> PartialEq for NextUse<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position
+    }
+}
+
+impl<K> Eq for NextUse<K> {}
+
+impl<K> PartialOrd for NextUse<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for NextUse<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.position.cmp(&other.position)
+    }
+}
 
 /// Implementation cache, based on a Bélády's algorithm.
-pub struct BeladyCache {
-    cache: Vec<i32>,
+///
+/// The algorithm needs to know the whole sequence of requests in advance, so `run` computes
+/// position of the next request for every request before checking (and starts with empty cache).
+/// Elements passed to `access` outside of `run` are treated as never requested again.
+///
+/// Elements of the cache are kept in the priority queue by position of their next request, so
+/// element for discarding is found in logarithmic time. Entries of the queue are not updated on
+/// hit, outdated entries are skipped on discarding.
+pub struct BeladyCache<K> {
+    cache: HashMap<K, usize>,
+    queue: BinaryHeap<NextUse<K>>,
+    next_use: Vec<usize>,
+    position: usize,
     size: usize,
     logger: slog::Logger,
 }
 
-impl BeladyCache {
+impl<K: Key> BeladyCache<K> {
     /// Create new cache with fix size.
     pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
         let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
         debug!(logger, "Created Belady cache with size: {}", size);
         BeladyCache {
-            cache: Vec::with_capacity(size),
+            cache: HashMap::with_capacity(size),
+            queue: BinaryHeap::with_capacity(size),
+            next_use: Vec::new(),
+            position: 0,
             size: size,
             logger: logger,
         }
     }
 
-    /// Checks the cache to find element. If the cache don't have element, checks the cache size
-    /// and removes old element for pushing new element.
-    ///
-    /// Return `true`, if the cache have element and `false` otherwise.
-    pub fn hit(&mut self, val: &i32, future: &VecDeque<i32>) -> bool {
-        if self.cache.contains(&val) {
+    // Remove the element that will not be needed for the longest time in the future.
+    fn look_into_the_future(&mut self) {
+        while let Some(next_use) = self.queue.pop() {
+            if self.cache.get(&next_use.key) == Some(&next_use.position) {
+                debug!(self.logger, "future usage of discarded element";
+                       "usage" => format!("{}", next_use.position));
+                self.cache.remove(&next_use.key);
+                break;
+            }
+        }
+    }
+
+    // Remember position of the next request of the element.
+    fn schedule(&mut self, key: K, position: usize) {
+        self.cache.insert(key.clone(), position);
+        self.queue.push(NextUse {
+            position: position,
+            key: key,
+        });
+        // Drop outdated entries, so the queue doesn't grow with every request.
+        if self.queue.len() > 2 * self.cache.len() + 16 {
+            self.queue = self.cache
+                .iter()
+                .map(|(key, &position)| {
+                    NextUse {
+                        position: position,
+                        key: key.clone(),
+                    }
+                })
+                .collect();
+        }
+    }
+}
+
+// Compute position of the next request (in order of all elements of all batches) for every
+// request. If the element will never be requested again, position is `NEVER`.
+fn next_use<K: Key>(ram: &Vec<Vec<K>>) -> Vec<usize> {
+    let count = ram.iter().map(|batch| batch.len()).sum();
+    let mut next_use = vec![NEVER; count];
+    let mut last_seen: HashMap<&K, usize> = HashMap::new();
+    let mut position = count;
+    for batch in ram.iter().rev() {
+        for elem in batch.iter().rev() {
+            position -= 1;
+            if let Some(next) = last_seen.insert(elem, position) {
+                next_use[position] = next;
+            }
+        }
+    }
+    next_use
+}
+
+impl<K: Key> CacheAlgorithm<K> for BeladyCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        let next = self.next_use.get(self.position).cloned().unwrap_or(NEVER);
+        self.position += 1;
+        if self.cache.contains_key(val) {
+            self.schedule(val.clone(), next);
             debug!(self.logger, "hit";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "hit" => format!("{:?}", val));
             true
         } else {
-            if self.cache.len() < self.size {
-                self.cache.push(val.clone());
-            } else {
-                self.look_into_the_future(future);
-
-                self.cache.push(val.clone());
+            if self.cache.len() >= self.size {
+                self.look_into_the_future();
             }
+            self.schedule(val.clone(), next);
             debug!(self.logger, "miss";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "hit" => format!("{:?}", val));
             false
         }
     }
 
-    // Remove the element that will not be needed for the longest time in the future.
-    fn look_into_the_future(&mut self, future: &VecDeque<i32>) {
-        let mut usage: Vec<Option<usize>> = Vec::with_capacity(self.cache.len());
-        for elem in self.cache.iter() {
-            let position_in_future = future.iter().position(|x| x == elem);
-            usage.push(position_in_future);
-        }
-        debug!(self.logger, "future usage of cache"; "usage" => format!("{:?}", usage));
-        let elem_for_remove = usage.iter().position(|x| x.is_none());
-        if let Some(index) = elem_for_remove {
-            self.cache.remove(index);
-        } else {
-            let index = usage.iter().position(|x| x == usage.iter().max().unwrap());
-            self.cache.remove(index.unwrap());
-        }
+    fn reset(&mut self) {
+        self.cache.clear();
+        self.queue.clear();
+        self.next_use.clear();
+        self.position = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
     }
 
-    /// Run process of checking algorithm.
-    ///
-    /// Return tuple with statistic: `(cache hit, cache miss)`.
-    pub fn run(&mut self, ram: &Vec<Vec<i32>>) -> (i32, i32) {
+    fn run(&mut self, ram: &Vec<Vec<K>>) -> (i32, i32) {
         let mut statistic = (0, 0);
-        let mut future: VecDeque<i32> = ram.iter()
-            .flat_map(|batch| {
-                batch.iter()
-                    .map(|elem| elem.clone())
-            })
-            .collect();
+        self.reset();
+        self.next_use = next_use(ram);
         for batch in ram {
             for elem in batch {
-                future.pop_front();
-                if self.hit(elem, &future) {
+                if self.access(elem) {
                     statistic.0 += 1;
                 } else {
                     statistic.1 += 1;
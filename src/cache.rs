@@ -0,0 +1,244 @@
+//! Implementation of key-value cache.
+//!
+//! See info about `Cache` structure. The cache is backed by the same cache algorithms (policies),
+//! which are used in the simulation, so the cache evicts exactly the same elements as the
+//! simulation does.
+
+use rand;
+use slog;
+
+use std::collections::HashMap;
+use std::collections::hash_map;
+
+use {Algos, CacheAlgorithm, Key};
+use fifo::FifoCache;
+use lfu::LFUCache;
+use lfuda::LFUDACache;
+use wlfu::WLFUCache;
+use lrfu::LRFUCache;
+use lru::LRUCache;
+use lruk::LRUKCache;
+use mru::MRUCache;
+use rr::RRCache;
+use slru::SLRUCache;
+use twoq::TwoQCache;
+use arc::ARCCache;
+use car::{CARCache, CARTCache};
+use mq::MQCache;
+use lirs::LIRSCache;
+use clock::ClockCache;
+use gclock::GClockCache;
+use nru::NRUCache;
+use sieve::SieveCache;
+use s3fifo::S3FifoCache;
+use tinylfu::{TinyLFUCache, WTinyLFUCache};
+use lecar::LeCaRCache;
+
+/// Cache algorithm, which may manage keys of the key-value cache.
+///
+/// In contrast to `CacheAlgorithm::access`, which puts missed element into the cache, operations
+/// of this trait split checking, updating and putting, so the caller decides, when the element is
+/// put into the cache.
+pub trait Policy<K>: CacheAlgorithm<K> {
+    /// Return `true`, if the cache have element.
+    fn contains(&self, key: &K) -> bool;
+
+    /// Update state of the element, which is in the cache (cache hit).
+    ///
+    /// Return evicted element, if the algorithm discards some element on hit.
+    fn touch(&mut self, key: &K) -> Option<K>;
+
+    /// Put the element, which is not in the cache, into the cache (cache miss).
+    ///
+    /// Return evicted element, if the cache was full.
+    fn insert(&mut self, key: K) -> Option<K>;
+
+    /// Remove the element from the cache.
+    ///
+    /// Return `true`, if the cache had element.
+    fn remove(&mut self, key: &K) -> bool;
+
+    /// Return element, which will be evicted by the next `insert`.
+    ///
+    /// `None` is returned, if the cache is not full, or if the algorithm can't tell the element
+    /// without changing its state (the default implementation).
+    fn victim(&self) -> Option<K> {
+        None
+    }
+}
+
+/// Create cache algorithm with fix size for the key-value cache.
+///
+/// Algorithms, which use random, are created with random seed. Return error, if the algorithm can
+/// not manage keys of the key-value cache (`BELADY`, `PLRU`, `BPLRU`, `CLOCKPRO` and `GDSF`).
+pub fn policy<K: Key + 'static>(algo: Algos,
+                                size: usize,
+                                logger: Option<slog::Logger>)
+                                -> Result<Box<dyn Policy<K>>, String> {
+    policy_with_seed(algo, size, rand::random(), logger)
+}
+
+/// Create cache algorithm with fix size for the key-value cache.
+///
+/// Algorithms, which use random, are created with the seed. Return error, if the algorithm can not
+/// manage keys of the key-value cache.
+pub fn policy_with_seed<K: Key + 'static>(algo: Algos,
+                                          size: usize,
+                                          seed: usize,
+                                          logger: Option<slog::Logger>)
+                                          -> Result<Box<dyn Policy<K>>, String> {
+    let policy: Box<dyn Policy<K>> = match algo {
+        Algos::FIFO => Box::new(FifoCache::new(size, logger)),
+        Algos::LRU => Box::new(LRUCache::new(size, logger)),
+        Algos::LFU => Box::new(LFUCache::new(size, logger)),
+        Algos::RR => Box::new(RRCache::with_seed(size, seed, logger)),
+        Algos::MRU => Box::new(MRUCache::new(size, logger)),
+        Algos::SLRU => Box::new(SLRUCache::new(size, logger)),
+        Algos::TwoQ => Box::new(TwoQCache::new(size, logger)),
+        Algos::ARC => Box::new(ARCCache::new(size, logger)),
+        Algos::CAR => Box::new(CARCache::new(size, logger)),
+        Algos::CART => Box::new(CARTCache::new(size, logger)),
+        Algos::MQ => Box::new(MQCache::new(size, logger)),
+        Algos::LIRS => Box::new(LIRSCache::new(size, logger)),
+        Algos::CLOCK => Box::new(ClockCache::new(size, logger)),
+        Algos::GCLOCK => Box::new(GClockCache::new(size, logger)),
+        Algos::NRU => Box::new(NRUCache::new(size, logger)),
+        Algos::SIEVE => Box::new(SieveCache::new(size, logger)),
+        Algos::S3FIFO => Box::new(S3FifoCache::new(size, logger)),
+        Algos::TinyLFU => Box::new(TinyLFUCache::new(size, logger)),
+        Algos::WTinyLFU => Box::new(WTinyLFUCache::new(size, logger)),
+        Algos::LRU2 => Box::new(LRUKCache::new(size, logger)),
+        Algos::LFUDA => Box::new(LFUDACache::new(size, logger)),
+        Algos::WLFU => Box::new(WLFUCache::new(size, logger)),
+        Algos::LRFU => Box::new(LRFUCache::new(size, logger)),
+        Algos::LeCaR => Box::new(LeCaRCache::with_seed(size, seed, logger)),
+        _ => return Err(format!("{} can not be used as a key-value cache policy", algo)),
+    };
+    Ok(policy)
+}
+
+/// Key-value cache with fix size.
+///
+/// Keys are managed by the cache algorithm, values are stored in the hash map. Only `get`,
+/// `get_mut` and `put` are counted by the cache algorithm as accesses.
+pub struct Cache<K, V> {
+    policy: Box<dyn Policy<K>>,
+    values: HashMap<K, V>,
+}
+
+impl<K: Key, V> Cache<K, V> {
+    /// Create new cache, managed by the cache algorithm.
+    pub fn new(policy: Box<dyn Policy<K>>) -> Self {
+        let capacity = policy.capacity();
+        Cache {
+            policy: policy,
+            values: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Create new cache with fix size, based on the `algo` algorithm.
+    ///
+    /// Return error, if the algorithm can not manage keys of the key-value cache.
+    pub fn with_algo(algo: Algos,
+                     size: usize,
+                     logger: Option<slog::Logger>)
+                     -> Result<Self, String>
+        where K: 'static
+    {
+        policy(algo, size, logger).map(Cache::new)
+    }
+
+    /// Return value of the key and mark the key as used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        if self.values.contains_key(key) {
+            self.touch(key);
+        }
+        self.values.get(key)
+    }
+
+    /// Return mutable value of the key and mark the key as used.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if self.values.contains_key(key) {
+            self.touch(key);
+        }
+        self.values.get_mut(key)
+    }
+
+    /// Put value of the key into the cache. If the cache already have key, the value is replaced.
+    ///
+    /// Return evicted entry, if the cache algorithm discarded some key.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.values.contains_key(&key) {
+            let evicted = self.touch(&key);
+            self.values.insert(key, value);
+            evicted
+        } else {
+            let evicted = self.policy.insert(key.clone());
+            self.values.insert(key, value);
+            evicted.and_then(|evicted| self.evict(evicted))
+        }
+    }
+
+    /// Remove the key from the cache.
+    ///
+    /// Return value of the key, if the cache had key.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.policy.remove(key);
+        self.values.remove(key)
+    }
+
+    /// Return `true`, if the cache have key.
+    pub fn contains(&self, key: &K) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Return value of the key without marking the key as used.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    /// Remove all keys from the cache.
+    pub fn clear(&mut self) {
+        self.policy.reset();
+        self.values.clear();
+    }
+
+    /// Maximum count of keys in the cache.
+    pub fn capacity(&self) -> usize {
+        self.policy.capacity()
+    }
+
+    /// Current count of keys in the cache.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return `true`, if the cache don't have keys.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterate over entries of the cache in arbitrary order without marking keys as used.
+    pub fn iter<'a>(&'a self) -> hash_map::Iter<'a, K, V> {
+        self.values.iter()
+    }
+
+    // Mark the key, which is in the cache, as used and drop value of evicted key.
+    fn touch(&mut self, key: &K) -> Option<(K, V)> {
+        self.policy.touch(key).and_then(|evicted| self.evict(evicted))
+    }
+
+    // Drop value of the key, which was discarded by the cache algorithm.
+    fn evict(&mut self, key: K) -> Option<(K, V)> {
+        self.values.remove(&key).map(|value| (key, value))
+    }
+}
+
+impl<'a, K: Key, V> IntoIterator for &'a Cache<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = hash_map::Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
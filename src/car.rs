@@ -0,0 +1,562 @@
+//! Implementation of CAR (Clock with Adaptive Replacement) and CART (CAR with Temporal filtering)
+//! cache algorithms.
+//!
+//! CAR combines ARC and CLOCK. Resident elements are kept in two clocks T1 and T2, every element
+//! has a reference bit, which is set on hit, so hit doesn't move elements. Elements, discarded from
+//! T1 and T2, are remembered (without data) in LRU lists B1 and B2. The target size of T1 `p` is
+//! adapted like in ARC. On discarding the hand of T1 (if T1 is greater than `p`) or T2 moves
+//! referenced elements to the tail of T2 and discards the first element, which is not referenced.
+//!
+//! CART additionally marks every element as short-term or long-term. Element becomes long-term,
+//! when it is referenced again after long period, so elements, which are requested several times
+//! during short period, are discarded like elements, which are requested once. The target size of
+//! B1 `q` is adapted too.
+//!
+//! In both clocks the head of the list is the element under the hand, new elements are put to the
+//! tail. In B1 and B2 the front is the most recently discarded element.
+
+use slog;
+
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+// List, which has the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Queue {
+    T1,
+    T2,
+    B1,
+    B2,
+}
+
+// Information about the element.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    queue: Queue,
+    index: usize,
+    referenced: bool,
+    long_term: bool,
+}
+
+// Clocks and ghost lists, shared by CAR and CART.
+struct Clocks<K> {
+    t1: List<K>,
+    t2: List<K>,
+    b1: List<K>,
+    b2: List<K>,
+    map: HashMap<K, Entry>,
+}
+
+impl<K: Key> Clocks<K> {
+    fn new(size: usize) -> Self {
+        Clocks {
+            t1: List::with_capacity(size),
+            t2: List::with_capacity(size),
+            b1: List::with_capacity(size),
+            b2: List::with_capacity(size),
+            map: HashMap::with_capacity(size * 2),
+        }
+    }
+
+    // List by its name.
+    fn list(&mut self, queue: Queue) -> &mut List<K> {
+        match queue {
+            Queue::T1 => &mut self.t1,
+            Queue::T2 => &mut self.t2,
+            Queue::B1 => &mut self.b1,
+            Queue::B2 => &mut self.b2,
+        }
+    }
+
+    fn is_resident(&self, key: &K) -> bool {
+        match self.map.get(key).map(|entry| entry.queue) {
+            Some(Queue::T1) | Some(Queue::T2) => true,
+            _ => false,
+        }
+    }
+
+    // Element under the hand of the clock.
+    fn head(&self, queue: Queue) -> Option<Entry> {
+        let list = match queue {
+            Queue::T1 => &self.t1,
+            Queue::T2 => &self.t2,
+            Queue::B1 => &self.b1,
+            Queue::B2 => &self.b2,
+        };
+        list.front().map(|index| self.map[list.get(index)])
+    }
+
+    // Move the element under the hand of the clock to the tail of other clock or to the front of
+    // the ghost list, reference bit is cleared.
+    fn move_head(&mut self, from: Queue, to: Queue) -> Option<K> {
+        self.list(from).pop_front().map(|key| {
+            let index = match to {
+                Queue::T1 | Queue::T2 => self.list(to).push_back(key.clone()),
+                Queue::B1 | Queue::B2 => self.list(to).push_front(key.clone()),
+            };
+            let entry = self.map.get_mut(&key).unwrap();
+            entry.queue = to;
+            entry.index = index;
+            entry.referenced = false;
+            key
+        })
+    }
+
+    // Forget the least recently discarded element of the ghost list.
+    fn forget(&mut self, queue: Queue) {
+        if let Some(key) = self.list(queue).pop_back() {
+            self.map.remove(&key);
+        }
+    }
+
+    // Put new element to the tail of the clock.
+    fn put(&mut self, key: K, queue: Queue, long_term: bool) {
+        let index = self.list(queue).push_back(key.clone());
+        self.map.insert(key,
+                        Entry {
+                            queue: queue,
+                            index: index,
+                            referenced: false,
+                            long_term: long_term,
+                        });
+    }
+
+    // Remove the element from its list.
+    fn take(&mut self, key: &K) -> Option<Entry> {
+        self.map.remove(key).map(|entry| {
+            self.list(entry.queue).remove(entry.index);
+            entry
+        })
+    }
+
+    fn clear(&mut self) {
+        self.t1.clear();
+        self.t2.clear();
+        self.b1.clear();
+        self.b2.clear();
+        self.map.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+}
+
+/// Implementation cache, based on a CAR algorithm.
+///
+/// Elements of B1 and B2 are not counted as elements of the cache. Every hit and miss is logged
+/// with the current value of `p`.
+pub struct CARCache<K> {
+    clocks: Clocks<K>,
+    p: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> CARCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created CAR cache with size: {}", size);
+        CARCache {
+            clocks: Clocks::new(size),
+            p: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Current target size of T1.
+    pub fn p(&self) -> usize {
+        self.p
+    }
+
+    // Discard the first not referenced element under the hand of T1 or T2.
+    fn replace(&mut self) -> Option<K> {
+        loop {
+            let (queue, ghost) = if self.clocks.t1.len() >= cmp::max(1, self.p) {
+                (Queue::T1, Queue::B1)
+            } else {
+                (Queue::T2, Queue::B2)
+            };
+            match self.clocks.head(queue) {
+                Some(ref entry) if entry.referenced => {
+                    self.clocks.move_head(queue, Queue::T2);
+                }
+                Some(_) => return self.clocks.move_head(queue, ghost),
+                None => return None,
+            }
+        }
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for CARCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "t1" => format!("{:?}", self.clocks.t1),
+                   "t2" => format!("{:?}", self.clocks.t2),
+                   "b1" => format!("{:?}", self.clocks.b1),
+                   "b2" => format!("{:?}", self.clocks.b2),
+                   "p" => self.p,
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "t1" => format!("{:?}", self.clocks.t1),
+                   "t2" => format!("{:?}", self.clocks.t2),
+                   "b1" => format!("{:?}", self.clocks.b1),
+                   "b2" => format!("{:?}", self.clocks.b2),
+                   "p" => self.p,
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.clocks.clear();
+        self.p = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.clocks.len()
+    }
+}
+
+impl<K: Key> Policy<K> for CARCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.clocks.is_resident(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if self.contains(key) {
+            self.clocks.map.get_mut(key).unwrap().referenced = true;
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let ghost = self.clocks.map.get(&key).map(|entry| entry.queue);
+        let mut evicted = None;
+        if self.clocks.len() >= self.size {
+            evicted = self.replace();
+            if ghost.is_none() {
+                let (t1, t2) = (self.clocks.t1.len(), self.clocks.t2.len());
+                let (b1, b2) = (self.clocks.b1.len(), self.clocks.b2.len());
+                if t1 + b1 >= self.size {
+                    self.clocks.forget(Queue::B1);
+                } else if t1 + t2 + b1 + b2 >= 2 * self.size {
+                    self.clocks.forget(Queue::B2);
+                }
+            }
+        }
+        let (b1, b2) = (self.clocks.b1.len(), self.clocks.b2.len());
+        match ghost {
+            Some(Queue::B1) => {
+                self.p = cmp::min(self.size, self.p + cmp::max(1, b2 / b1));
+                debug!(self.logger, "hit in B1"; "p" => self.p);
+                self.clocks.take(&key);
+                self.clocks.put(key, Queue::T2, true);
+            }
+            Some(Queue::B2) => {
+                self.p = self.p.saturating_sub(cmp::max(1, b1 / b2));
+                debug!(self.logger, "hit in B2"; "p" => self.p);
+                self.clocks.take(&key);
+                self.clocks.put(key, Queue::T2, true);
+            }
+            _ => self.clocks.put(key, Queue::T1, false),
+        }
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if self.contains(key) {
+            self.clocks.take(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Follow the hands like `replace` does, but without moving elements.
+    fn victim(&self) -> Option<K> {
+        if self.clocks.len() < self.size {
+            return None;
+        }
+        let clocks = &self.clocks;
+        // Referenced elements of T1 are moved to the tail of T2, while T1 is not less than `p`.
+        let mut t1 = clocks.t1.len();
+        let mut moved = None;
+        let mut index = clocks.t1.front();
+        while let Some(current) = index {
+            if t1 < cmp::max(1, self.p) {
+                break;
+            }
+            let key = clocks.t1.get(current);
+            if !clocks.map[key].referenced {
+                return Some(key.clone());
+            }
+            moved = moved.or(Some(key));
+            t1 -= 1;
+            index = clocks.t1.next(current);
+        }
+        // The hand of T2 clears reference bits, so the head is discarded, if all elements are
+        // referenced.
+        clocks.t2
+            .iter()
+            .find(|key| !clocks.map[*key].referenced)
+            .or(moved)
+            .or(clocks.t2.iter().next())
+            .cloned()
+    }
+}
+
+/// Implementation cache, based on a CART algorithm.
+///
+/// Elements of B1 and B2 are not counted as elements of the cache. Every hit and miss is logged
+/// with the current values of `p` and `q`.
+pub struct CARTCache<K> {
+    clocks: Clocks<K>,
+    p: usize,
+    q: usize,
+    count_short: usize,
+    count_long: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> CARTCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created CART cache with size: {}", size);
+        CARTCache {
+            clocks: Clocks::new(size),
+            p: 0,
+            q: 0,
+            count_short: 0,
+            count_long: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Current target size of T1.
+    pub fn p(&self) -> usize {
+        self.p
+    }
+
+    /// Current target size of B1.
+    pub fn q(&self) -> usize {
+        self.q
+    }
+
+    // Increase `q`, if there are too many long-term elements.
+    fn increase_q(&mut self) {
+        let (t1, t2, b2) = (self.clocks.t1.len(), self.clocks.t2.len(), self.clocks.b2.len());
+        if t1 + t2 + b2 >= self.size + self.count_short {
+            self.q = cmp::min(self.q + 1, (2 * self.size).saturating_sub(t1));
+        }
+    }
+
+    fn decrease_q(&mut self) {
+        let t1 = self.clocks.t1.len();
+        self.q = cmp::max(self.q.saturating_sub(1), self.size.saturating_sub(t1));
+    }
+
+    // Discard the first not referenced short-term element under the hand of T1 or the first not
+    // referenced element under the hand of T2.
+    fn replace(&mut self) -> Option<K> {
+        while self.clocks.head(Queue::T2).map_or(false, |entry| entry.referenced) {
+            self.clocks.move_head(Queue::T2, Queue::T1);
+            self.increase_q();
+        }
+        while let Some(entry) = self.clocks.head(Queue::T1) {
+            if entry.referenced {
+                let key = self.clocks.move_head(Queue::T1, Queue::T1).unwrap();
+                let t1 = self.clocks.t1.len();
+                if t1 >= cmp::min(self.p + 1, self.clocks.b1.len()) && !entry.long_term {
+                    self.clocks.map.get_mut(&key).unwrap().long_term = true;
+                    self.count_short -= 1;
+                    self.count_long += 1;
+                }
+            } else if entry.long_term {
+                self.clocks.move_head(Queue::T1, Queue::T2);
+                self.decrease_q();
+            } else {
+                break;
+            }
+        }
+        let t1 = self.clocks.t1.len();
+        let (from, to) = if (t1 >= cmp::max(1, self.p) || self.clocks.t2.len() == 0) && t1 > 0 {
+            (Queue::T1, Queue::B1)
+        } else {
+            (Queue::T2, Queue::B2)
+        };
+        let long_term = self.clocks.head(from).map(|entry| entry.long_term);
+        match long_term {
+            Some(true) => self.count_long -= 1,
+            Some(false) => self.count_short -= 1,
+            None => {}
+        }
+        self.clocks.move_head(from, to)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for CARTCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "t1" => format!("{:?}", self.clocks.t1),
+                   "t2" => format!("{:?}", self.clocks.t2),
+                   "b1" => format!("{:?}", self.clocks.b1),
+                   "b2" => format!("{:?}", self.clocks.b2),
+                   "p" => self.p,
+                   "q" => self.q,
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "t1" => format!("{:?}", self.clocks.t1),
+                   "t2" => format!("{:?}", self.clocks.t2),
+                   "b1" => format!("{:?}", self.clocks.b1),
+                   "b2" => format!("{:?}", self.clocks.b2),
+                   "p" => self.p,
+                   "q" => self.q,
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.clocks.clear();
+        self.p = 0;
+        self.q = 0;
+        self.count_short = 0;
+        self.count_long = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.clocks.len()
+    }
+}
+
+impl<K: Key> Policy<K> for CARTCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.clocks.is_resident(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if self.contains(key) {
+            self.clocks.map.get_mut(key).unwrap().referenced = true;
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let ghost = self.clocks.map.get(&key).map(|entry| entry.queue);
+        let mut evicted = None;
+        if self.clocks.len() >= self.size {
+            evicted = self.replace();
+            let (b1, b2) = (self.clocks.b1.len(), self.clocks.b2.len());
+            if ghost.is_none() && b1 + b2 > self.size {
+                if b1 > self.q || b2 == 0 {
+                    self.clocks.forget(Queue::B1);
+                } else {
+                    self.clocks.forget(Queue::B2);
+                }
+            }
+        }
+        let (b1, b2) = (self.clocks.b1.len(), self.clocks.b2.len());
+        match ghost {
+            Some(Queue::B1) => {
+                self.p = cmp::min(self.size, self.p + cmp::max(1, self.count_short / b1));
+                debug!(self.logger, "hit in B1"; "p" => self.p);
+                self.clocks.take(&key);
+                self.clocks.put(key, Queue::T1, true);
+                self.count_long += 1;
+            }
+            Some(Queue::B2) => {
+                self.p = self.p.saturating_sub(cmp::max(1, self.count_long / b2));
+                debug!(self.logger, "hit in B2"; "p" => self.p);
+                self.clocks.take(&key);
+                self.clocks.put(key, Queue::T1, true);
+                self.count_long += 1;
+                self.increase_q();
+            }
+            _ => {
+                self.clocks.put(key, Queue::T1, false);
+                self.count_short += 1;
+            }
+        }
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if self.contains(key) {
+            if self.clocks.take(key).unwrap().long_term {
+                self.count_long -= 1;
+            } else {
+                self.count_short -= 1;
+            }
+            true
+        } else {
+            false
+        }
+    }
+    // Follow the hands like `replace` does, but without moving elements.
+    fn victim(&self) -> Option<K> {
+        if self.clocks.len() < self.size {
+            return None;
+        }
+        let clocks = &self.clocks;
+        // Referenced elements under the hand of T2 are moved to the tail of T1.
+        let t2_head = clocks.t2.iter().find(|key| !clocks.map[*key].referenced);
+        let mut t1: VecDeque<(&K, bool, bool)> = clocks.t1
+            .iter()
+            .map(|key| (key, clocks.map[key].referenced, clocks.map[key].long_term))
+            .chain(clocks.t2
+                .iter()
+                .take_while(|key| clocks.map[*key].referenced)
+                .map(|key| (key, false, clocks.map[key].long_term)))
+            .collect();
+        // Referenced elements of T1 are moved to the tail of T1, not referenced long-term elements
+        // are moved to the tail of T2.
+        let mut t1_len = t1.len();
+        let mut t2_tail = None;
+        while let Some((key, referenced, long_term)) = t1.pop_front() {
+            if referenced {
+                let long_term = long_term || t1_len >= cmp::min(self.p + 1, clocks.b1.len());
+                t1.push_back((key, false, long_term));
+            } else if long_term {
+                t1_len -= 1;
+                t2_tail = t2_tail.or(Some(key));
+            } else {
+                t1.push_front((key, referenced, long_term));
+                break;
+            }
+        }
+        let t2_head = t2_head.or(t2_tail);
+        if (t1_len >= cmp::max(1, self.p) || t2_head.is_none()) && t1_len > 0 {
+            t1.front().map(|&(key, _, _)| key.clone())
+        } else {
+            t2_head.cloned()
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Implementation of CLOCK (second chance) cache algorithm.
+//!
+//! Elements are kept in a circular buffer with a reference bit for every element. The bit is set
+//! when the element is requested. On discarding the hand of the clock goes over elements, clears
+//! set bits and stops at the first element with cleared bit. So CLOCK works like FIFO, which gives
+//! requested elements a second chance, and approximates LRU without moving elements on hit.
+
+use slog;
+
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+
+/// Implementation cache, based on a CLOCK algorithm.
+///
+/// New element is put with set reference bit, after the element is put the hand points to the next
+/// element.
+pub struct ClockCache<K> {
+    slots: Vec<Option<K>>,
+    referenced: Vec<bool>,
+    map: HashMap<K, usize>,
+    free: Vec<usize>,
+    hand: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> ClockCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created CLOCK cache with size: {}", size);
+        ClockCache {
+            slots: Vec::with_capacity(size),
+            referenced: Vec::with_capacity(size),
+            map: HashMap::with_capacity(size),
+            free: Vec::new(),
+            hand: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    // Move the hand to the first element with cleared bit, clearing set bits.
+    fn sweep(&mut self) -> usize {
+        while self.referenced[self.hand] {
+            self.referenced[self.hand] = false;
+            self.hand = (self.hand + 1) % self.slots.len();
+        }
+        let slot = self.hand;
+        self.hand = (self.hand + 1) % self.slots.len();
+        slot
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for ClockCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.slots),
+                   "referenced" => format!("{:?}", self.referenced),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.slots),
+                   "referenced" => format!("{:?}", self.referenced),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.slots.clear();
+        self.referenced.clear();
+        self.map.clear();
+        self.free.clear();
+        self.hand = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<K: Key> Policy<K> for ClockCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(&slot) = self.map.get(key) {
+            self.referenced[slot] = true;
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let (slot, evicted) = if let Some(slot) = self.free.pop() {
+            (slot, None)
+        } else if self.slots.len() < self.size || self.slots.is_empty() {
+            // The cache with zero size keeps the last element, as `LRUCache` does.
+            self.slots.push(None);
+            self.referenced.push(false);
+            (self.slots.len() - 1, None)
+        } else {
+            let slot = self.sweep();
+            (slot, self.slots[slot].take())
+        };
+        if let Some(ref evicted) = evicted {
+            self.map.remove(evicted);
+        }
+        self.slots[slot] = Some(key.clone());
+        self.referenced[slot] = true;
+        self.map.insert(key, slot);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(slot) = self.map.remove(key) {
+            self.slots[slot] = None;
+            self.free.push(slot);
+            true
+        } else {
+            false
+        }
+    }
+    fn victim(&self) -> Option<K> {
+        if !self.free.is_empty() || self.slots.len() < self.size || self.slots.is_empty() {
+            return None;
+        }
+        // The first element with cleared bit after the hand, or the element under the hand, if all
+        // bits are set.
+        let count = self.slots.len();
+        let slot = (0..count)
+            .map(|distance| (self.hand + distance) % count)
+            .find(|&slot| !self.referenced[slot])
+            .unwrap_or(self.hand);
+        self.slots[slot].clone()
+    }
+}
@@ -0,0 +1,280 @@
+//! Implementation of CLOCK-Pro cache algorithm.
+//!
+//! CLOCK-Pro brings the idea of LIRS (reuse distance instead of recency) to CLOCK, it is used as
+//! a base of page replacement in some operating systems. Pages are hot (like LIR elements) or cold
+//! (like resident HIR elements), cold pages are in the test period after putting into the cache.
+//! Cold page, which is requested during its test period, becomes hot. Discarded cold pages are
+//! remembered as non-resident (test) pages, so requested test page is put into the cache as hot.
+//!
+//! All pages are kept in one clock, which has three hands:
+//!
+//!   - the cold hand discards not referenced cold pages (they become test pages) and makes
+//!     referenced cold pages hot;
+//!   - the hot hand makes not referenced hot pages cold, when there are too many hot pages;
+//!   - the test hand forgets test pages, when there are too many test pages.
+//!
+//! The part of the cache for cold pages is adapted: it is increased, when test page is requested,
+//! and decreased, when test page is forgotten.
+//!
+//! The implementation follows the simplified design of the `go-clockpro` library, except that the
+//! hot hand is run only while making room for new page.
+
+use slog;
+
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use list::List;
+
+// Type of the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Page {
+    Hot,
+    Cold,
+    Test,
+}
+
+// Page in the clock.
+#[derive(Debug)]
+struct Entry<K> {
+    key: K,
+    page: Page,
+    referenced: bool,
+}
+
+/// Implementation cache, based on a CLOCK-Pro algorithm.
+///
+/// The clock is the linked list, the back of the list is followed by the front. New pages are put
+/// right before the hot hand. At most size of the cache test pages are remembered.
+pub struct ClockProCache<K> {
+    ring: List<Entry<K>>,
+    map: HashMap<K, usize>,
+    hand_hot: Option<usize>,
+    hand_cold: Option<usize>,
+    hand_test: Option<usize>,
+    count_hot: usize,
+    count_cold: usize,
+    count_test: usize,
+    size_cold: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> ClockProCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created CLOCK-Pro cache with size: {}", size);
+        ClockProCache {
+            ring: List::with_capacity(size * 2),
+            map: HashMap::with_capacity(size * 2),
+            hand_hot: None,
+            hand_cold: None,
+            hand_test: None,
+            count_hot: 0,
+            count_cold: 0,
+            count_test: 0,
+            size_cold: size,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Current target count of cold pages.
+    pub fn cold_target(&self) -> usize {
+        self.size_cold
+    }
+
+    // Next page of the clock.
+    fn next(&self, index: usize) -> usize {
+        self.ring.next(index).or(self.ring.front()).unwrap()
+    }
+
+    // Previous page of the clock.
+    fn prev(&self, index: usize) -> usize {
+        self.ring.prev(index).or(self.ring.back()).unwrap()
+    }
+
+    // Put new page before the hot hand, making room for it.
+    fn add(&mut self, entry: Entry<K>) {
+        while self.count_hot + self.count_cold >= self.size && self.hand_cold.is_some() {
+            self.run_hand_cold();
+            // Balancing is not done inside of `run_hand_cold`, because the test hand runs the cold
+            // hand, and all hands point to the same page, if the clock has only one page.
+            while self.count_hot > self.size - self.size_cold && self.hand_hot.is_some() {
+                self.run_hand_hot();
+            }
+        }
+        let key = entry.key.clone();
+        let index = match self.hand_hot {
+            Some(hand) => self.ring.insert_before(hand, entry),
+            None => self.ring.push_back(entry),
+        };
+        self.map.insert(key, index);
+        if self.hand_hot.is_none() {
+            self.hand_hot = Some(index);
+            self.hand_cold = Some(index);
+            self.hand_test = Some(index);
+        }
+        if self.hand_cold == self.hand_hot {
+            self.hand_cold = self.hand_cold.map(|hand| self.prev(hand));
+        }
+    }
+
+    // Remove the page from the clock, hands, which point to the page, are moved back.
+    fn delete(&mut self, index: usize) {
+        let prev = self.prev(index);
+        for hand in &mut [&mut self.hand_hot, &mut self.hand_cold, &mut self.hand_test] {
+            if **hand == Some(index) {
+                **hand = Some(prev);
+            }
+        }
+        let entry = self.ring.remove(index);
+        self.map.remove(&entry.key);
+        if self.ring.len() == 0 {
+            self.hand_hot = None;
+            self.hand_cold = None;
+            self.hand_test = None;
+        }
+    }
+
+    fn run_hand_cold(&mut self) {
+        let index = self.hand_cold.unwrap();
+        let discarded = {
+            let entry = self.ring.get_mut(index);
+            match (entry.page, entry.referenced) {
+                (Page::Cold, true) => {
+                    entry.page = Page::Hot;
+                    entry.referenced = false;
+                    self.count_cold -= 1;
+                    self.count_hot += 1;
+                    false
+                }
+                (Page::Cold, false) => {
+                    entry.page = Page::Test;
+                    self.count_cold -= 1;
+                    self.count_test += 1;
+                    true
+                }
+                _ => false,
+            }
+        };
+        if discarded {
+            debug!(self.logger, "discard";
+                   "page" => format!("{:?}", self.ring.get(index).key));
+            while self.count_test > self.size && self.hand_test.is_some() {
+                self.run_hand_test();
+            }
+        }
+        self.hand_cold = self.hand_cold.map(|hand| self.next(hand));
+    }
+
+    fn run_hand_hot(&mut self) {
+        if self.hand_hot == self.hand_test {
+            self.run_hand_test();
+        }
+        let index = match self.hand_hot {
+            Some(index) => index,
+            None => return,
+        };
+        {
+            let entry = self.ring.get_mut(index);
+            if entry.page == Page::Hot {
+                if entry.referenced {
+                    entry.referenced = false;
+                } else {
+                    entry.page = Page::Cold;
+                    self.count_hot -= 1;
+                    self.count_cold += 1;
+                }
+            }
+        }
+        self.hand_hot = Some(self.next(index));
+    }
+
+    fn run_hand_test(&mut self) {
+        if self.hand_test == self.hand_cold {
+            self.run_hand_cold();
+        }
+        let index = match self.hand_test {
+            Some(index) => index,
+            None => return,
+        };
+        if self.ring.get(index).page == Page::Test {
+            self.delete(index);
+            self.count_test -= 1;
+            if self.size_cold > 1 {
+                self.size_cold -= 1;
+            }
+        }
+        self.hand_test = self.hand_test.map(|hand| self.next(hand));
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for ClockProCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        let found = self.map.get(val).map(|&index| (index, self.ring.get(index).page));
+        match found {
+            Some((index, Page::Hot)) |
+            Some((index, Page::Cold)) => {
+                self.ring.get_mut(index).referenced = true;
+                debug!(self.logger, "hit";
+                       "cache" => format!("{:?}", self.ring),
+                       "cold target" => self.size_cold,
+                       "hit" => format!("{:?}", val));
+                true
+            }
+            Some((index, Page::Test)) => {
+                if self.size_cold < self.size {
+                    self.size_cold += 1;
+                }
+                self.count_test -= 1;
+                self.delete(index);
+                self.add(Entry {
+                    key: val.clone(),
+                    page: Page::Hot,
+                    referenced: false,
+                });
+                self.count_hot += 1;
+                debug!(self.logger, "miss";
+                       "cache" => format!("{:?}", self.ring),
+                       "cold target" => self.size_cold,
+                       "hit" => format!("{:?}", val));
+                false
+            }
+            None => {
+                self.add(Entry {
+                    key: val.clone(),
+                    page: Page::Cold,
+                    referenced: false,
+                });
+                self.count_cold += 1;
+                debug!(self.logger, "miss";
+                       "cache" => format!("{:?}", self.ring),
+                       "cold target" => self.size_cold,
+                       "hit" => format!("{:?}", val));
+                false
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.ring.clear();
+        self.map.clear();
+        self.hand_hot = None;
+        self.hand_cold = None;
+        self.hand_test = None;
+        self.count_hot = 0;
+        self.count_cold = 0;
+        self.count_test = 0;
+        self.size_cold = self.size;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.count_hot + self.count_cold
+    }
+}
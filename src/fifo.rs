@@ -5,67 +5,105 @@
 
 use slog;
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
 
 /// Implementation cache, based on a FIFO algorithm.
 ///
-/// Cache data is double-ended queue, which has limit of number of elements.
-pub struct FifoCache {
-    cache: VecDeque<i32>,
+/// Cache data is double-ended queue, which has limit of number of elements. The hash set of
+/// elements is used for checking the cache in constant time.
+pub struct FifoCache<K> {
+    cache: VecDeque<K>,
+    set: HashSet<K>,
     size: usize,
     logger: slog::Logger,
 }
 
-impl FifoCache {
+impl<K: Key> FifoCache<K> {
     /// Create new cache with fix size.
     pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
         let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
         debug!(logger, "Created FIFO cache with size: {}", size);
         FifoCache {
             cache: VecDeque::with_capacity(size),
+            set: HashSet::with_capacity(size),
             size: size,
             logger: logger,
         }
     }
+}
 
-    /// Checks the cache to find element. If the cache don't have element, checks the cache size
-    /// and removes old element for pushing new element.
-    ///
-    /// Return `true`, if the cache have element and `false` otherwise.
-    pub fn hit(&mut self, val: &i32) -> bool {
-        if self.cache.contains(&val) {
+impl<K: Key> CacheAlgorithm<K> for FifoCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
             debug!(self.logger, "hit";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "hit" => format!("{:?}", val));
             true
         } else {
-            if self.cache.len() < self.size {
-                self.cache.push_front(val.clone());
-            } else {
-                self.cache.pop_back();
-                self.cache.push_front(val.clone());
-            }
+            self.insert(val.clone());
             debug!(self.logger, "miss";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.cache.clear();
+        self.set.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+impl<K: Key> Policy<K> for FifoCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.set.contains(key)
+    }
+
+    fn touch(&mut self, _key: &K) -> Option<K> {
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.cache.len() < self.size {
+            None
+        } else {
+            self.cache.pop_back()
+        };
+        if let Some(ref evicted) = evicted {
+            self.set.remove(evicted);
+        }
+        self.set.insert(key.clone());
+        self.cache.push_front(key);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if self.set.remove(key) {
+            let position = self.cache.iter().position(|x| x == key).unwrap();
+            self.cache.remove(position);
+            true
+        } else {
             false
         }
     }
 
-    /// Run process of checking algorithm.
-    ///
-    /// Return tuple with statistic: `(cache hit, cache miss)`.
-    pub fn run(&mut self, ram: &Vec<Vec<i32>>) -> (i32, i32) {
-        let mut statistic = (0, 0);
-        for batch in ram {
-            for elem in batch {
-                if self.hit(elem) {
-                    statistic.0 += 1;
-                } else {
-                    statistic.1 += 1;
-                }
-            }
+    fn victim(&self) -> Option<K> {
+        if self.cache.len() < self.size {
+            None
+        } else {
+            self.cache.back().cloned()
         }
-        statistic
     }
 }
@@ -0,0 +1,160 @@
+//! Implementation of GCLOCK (generalized CLOCK) cache algorithm.
+//!
+//! GCLOCK replaces the reference bit of CLOCK by a counter, which is incremented on every request of
+//! the element (until it reaches its maximum). On discarding the hand of the clock decrements
+//! counters and stops at the first element with zero counter, so frequently requested elements
+//! survive several turns of the hand. With one bit counters GCLOCK is the same as CLOCK.
+
+use slog;
+
+use std::cmp;
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+
+/// Implementation cache, based on a GCLOCK algorithm.
+///
+/// New element is put with counter equal to one. By default counters have 2 bits.
+pub struct GClockCache<K> {
+    slots: Vec<Option<K>>,
+    counters: Vec<u8>,
+    map: HashMap<K, usize>,
+    free: Vec<usize>,
+    hand: usize,
+    max_count: u8,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> GClockCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created GCLOCK cache with size: {}", size);
+        GClockCache {
+            slots: Vec::with_capacity(size),
+            counters: Vec::with_capacity(size),
+            map: HashMap::with_capacity(size),
+            free: Vec::new(),
+            hand: 0,
+            max_count: 3,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set count of bits of counters.
+    ///
+    /// Panics, if the count is not in `1..=8` range.
+    pub fn with_bits(mut self, bits: u32) -> Self {
+        assert!((1..=8).contains(&bits), "Count of bits must be in 1..=8 range");
+        self.max_count = ((1u16 << bits) - 1) as u8;
+        debug!(self.logger, format!("With {} bits counters.", bits));
+        self
+    }
+
+    // Move the hand to the first element with zero counter, decrementing other counters.
+    fn sweep(&mut self) -> usize {
+        while self.counters[self.hand] > 0 {
+            self.counters[self.hand] -= 1;
+            self.hand = (self.hand + 1) % self.slots.len();
+        }
+        let slot = self.hand;
+        self.hand = (self.hand + 1) % self.slots.len();
+        slot
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for GClockCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.slots),
+                   "counters" => format!("{:?}", self.counters),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.slots),
+                   "counters" => format!("{:?}", self.counters),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.slots.clear();
+        self.counters.clear();
+        self.map.clear();
+        self.free.clear();
+        self.hand = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<K: Key> Policy<K> for GClockCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(&slot) = self.map.get(key) {
+            self.counters[slot] = cmp::min(self.counters[slot].saturating_add(1), self.max_count);
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let (slot, evicted) = if let Some(slot) = self.free.pop() {
+            (slot, None)
+        } else if self.slots.len() < self.size || self.slots.is_empty() {
+            // The cache with zero size keeps the last element, as `LRUCache` does.
+            self.slots.push(None);
+            self.counters.push(0);
+            (self.slots.len() - 1, None)
+        } else {
+            let slot = self.sweep();
+            (slot, self.slots[slot].take())
+        };
+        if let Some(ref evicted) = evicted {
+            self.map.remove(evicted);
+        }
+        self.slots[slot] = Some(key.clone());
+        self.counters[slot] = 1;
+        self.map.insert(key, slot);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(slot) = self.map.remove(key) {
+            self.slots[slot] = None;
+            self.free.push(slot);
+            true
+        } else {
+            false
+        }
+    }
+    fn victim(&self) -> Option<K> {
+        if !self.free.is_empty() || self.slots.len() < self.size || self.slots.is_empty() {
+            return None;
+        }
+        // Every pass of the hand decrements counters, so the element with the least counter is
+        // discarded, the nearest to the hand among equal.
+        let count = self.slots.len();
+        let slot = (0..count)
+            .map(|distance| (self.hand + distance) % count)
+            .min_by_key(|&slot| self.counters[slot])
+            .unwrap();
+        self.slots[slot].clone()
+    }
+}
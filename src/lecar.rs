@@ -0,0 +1,308 @@
+//! Implementation of LeCaR (Learning Cache Replacement) cache algorithm.
+//!
+//! The best cache algorithm depends on the workload, LeCaR chooses between two experts, LRU and LFU,
+//! online. Both experts see the same elements of the cache, on discarding the expert is chosen
+//! randomly with probability equal to its weight. The discarded element is remembered (without
+//! data) in the history of the chosen expert. If the element from the history is requested, the
+//! expert made a mistake, so the weight of the other expert is increased (regret minimization).
+//! The reward is discounted with time, which the element has spent in the history.
+//!
+//! The element, which is discarded by both experts, is not remembered. The element from the history
+//! gets its count of requests back.
+
+use slog;
+use rand::{self, Rng, SeedableRng, StdRng};
+
+use std::collections::{BTreeMap, HashMap};
+use std::mem;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+// Expert, which chose the element for discarding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expert {
+    LRU,
+    LFU,
+}
+
+// Information about the element in the cache.
+struct Entry {
+    freq: usize,
+    last: u64,
+    index: usize,
+}
+
+// Information about the element in the history.
+struct Ghost {
+    expert: Expert,
+    index: usize,
+    time: u64,
+    freq: usize,
+}
+
+/// Implementation cache, based on a LeCaR algorithm.
+///
+/// Elements are kept in the linked list from the most to the least recently used (for LRU) and in
+/// the ordered map by count of requests and time of the last request (for LFU, ties are broken by
+/// LRU, like in `LFUCache`). The history of every expert has size of the cache. By default the
+/// learning rate is `0.45` and the discount rate is `0.005^(1/size)`.
+///
+/// The expert is chosen by the generator of random, the cache, created with the same seed, discards
+/// the same elements on the same requests. The random number is taken in advance, so
+/// `Policy::victim` tells the element, which will be discarded.
+pub struct LeCaRCache<K> {
+    recency: List<K>,
+    frequency: BTreeMap<(usize, u64), K>,
+    entries: HashMap<K, Entry>,
+    history_lru: List<K>,
+    history_lfu: List<K>,
+    ghosts: HashMap<K, Ghost>,
+    weight_lru: f64,
+    weight_lfu: f64,
+    learning_rate: f64,
+    discount: f64,
+    time: u64,
+    rng: StdRng,
+    draw: f64,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> LeCaRCache<K> {
+    /// Create new cache with fix size and random seed.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        LeCaRCache::with_seed(size, rand::random(), logger)
+    }
+
+    /// Create new cache with fix size and the seed of random.
+    pub fn with_seed(size: usize, seed: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created LeCaR cache with size: {}", size);
+        debug!(logger, "With seed: {}", seed);
+        let mut rng = StdRng::from_seed(&[seed][..]);
+        let draw = rng.next_f64();
+        LeCaRCache {
+            recency: List::with_capacity(size),
+            frequency: BTreeMap::new(),
+            entries: HashMap::with_capacity(size),
+            history_lru: List::with_capacity(size),
+            history_lfu: List::with_capacity(size),
+            ghosts: HashMap::with_capacity(2 * size),
+            weight_lru: 0.5,
+            weight_lfu: 0.5,
+            learning_rate: 0.45,
+            discount: 0.005f64.powf(1.0 / size as f64),
+            time: 0,
+            rng: rng,
+            draw: draw,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set the learning rate.
+    ///
+    /// Panics, if the rate is not positive.
+    pub fn with_learning_rate(mut self, rate: f64) -> Self {
+        assert!(rate > 0.0, "Learning rate must be positive");
+        self.learning_rate = rate;
+        debug!(self.logger, format!("With learning rate: {}", self.learning_rate));
+        self
+    }
+
+    /// Current weights of LRU and LFU experts, the sum of weights is one.
+    pub fn weights(&self) -> (f64, f64) {
+        (self.weight_lru, self.weight_lfu)
+    }
+
+    // Increase weight of the expert, which didn't discard the requested element of the history.
+    fn learn(&mut self, ghost: &Ghost) {
+        let reward = self.discount.powf((self.time - ghost.time) as f64);
+        let factor = (self.learning_rate * reward).exp();
+        match ghost.expert {
+            Expert::LRU => self.weight_lfu *= factor,
+            Expert::LFU => self.weight_lru *= factor,
+        }
+        let sum = self.weight_lru + self.weight_lfu;
+        self.weight_lru /= sum;
+        self.weight_lfu /= sum;
+    }
+
+    // Remember the discarded element in the history of the expert.
+    fn remember(&mut self, key: K, expert: Expert, freq: usize) {
+        let time = self.time;
+        let size = self.size;
+        let forgotten = {
+            let history = match expert {
+                Expert::LRU => &mut self.history_lru,
+                Expert::LFU => &mut self.history_lfu,
+            };
+            let index = history.push_front(key.clone());
+            self.ghosts.insert(key,
+                               Ghost {
+                                   expert: expert,
+                                   index: index,
+                                   time: time,
+                                   freq: freq,
+                               });
+            if history.len() > size {
+                history.pop_back()
+            } else {
+                None
+            }
+        };
+        if let Some(forgotten) = forgotten {
+            self.ghosts.remove(&forgotten);
+        }
+    }
+
+    // Remove the element from the history, if the history has it.
+    fn forget(&mut self, key: &K) -> Option<Ghost> {
+        self.ghosts.remove(key).map(|ghost| {
+            match ghost.expert {
+                Expert::LRU => self.history_lru.remove(ghost.index),
+                Expert::LFU => self.history_lfu.remove(ghost.index),
+            };
+            ghost
+        })
+    }
+
+    // Discard the element, chosen by one of experts.
+    fn evict(&mut self) -> Option<K> {
+        let lru = match self.recency.back() {
+            Some(index) => self.recency.get(index).clone(),
+            None => return None,
+        };
+        let lfu = self.frequency.values().next().unwrap().clone();
+        let (evicted, expert) = if lru == lfu {
+            (lru, None)
+        } else if mem::replace(&mut self.draw, self.rng.next_f64()) < self.weight_lru {
+            (lru, Some(Expert::LRU))
+        } else {
+            (lfu, Some(Expert::LFU))
+        };
+        let entry = self.entries.remove(&evicted).unwrap();
+        self.recency.remove(entry.index);
+        self.frequency.remove(&(entry.freq, entry.last));
+        if let Some(expert) = expert {
+            debug!(self.logger, "discard";
+                   "element" => format!("{:?}", evicted),
+                   "expert" => format!("{:?}", expert));
+            self.remember(evicted.clone(), expert, entry.freq);
+        }
+        Some(evicted)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for LeCaRCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.recency),
+                   "weight lru" => self.weight_lru,
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.recency),
+                   "weight lru" => self.weight_lru,
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.recency.clear();
+        self.frequency.clear();
+        self.entries.clear();
+        self.history_lru.clear();
+        self.history_lfu.clear();
+        self.ghosts.clear();
+        self.weight_lru = 0.5;
+        self.weight_lfu = 0.5;
+        self.time = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K: Key> Policy<K> for LeCaRCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        self.time += 1;
+        let time = self.time;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.move_to_front(entry.index);
+            self.frequency.remove(&(entry.freq, entry.last));
+            entry.freq += 1;
+            entry.last = time;
+            self.frequency.insert((entry.freq, entry.last), key.clone());
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.time += 1;
+        let freq = match self.forget(&key) {
+            Some(ghost) => {
+                self.learn(&ghost);
+                ghost.freq + 1
+            }
+            None => 1,
+        };
+        let evicted = if self.entries.len() < self.size {
+            None
+        } else {
+            self.evict()
+        };
+        let index = self.recency.push_front(key.clone());
+        self.frequency.insert((freq, self.time), key.clone());
+        self.entries.insert(key,
+                            Entry {
+                                freq: freq,
+                                last: self.time,
+                                index: index,
+                            });
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(entry.index);
+            self.frequency.remove(&(entry.freq, entry.last));
+            true
+        } else {
+            false
+        }
+    }
+    // Element from the history changes weights before discarding, so the element is told for new
+    // element.
+    fn victim(&self) -> Option<K> {
+        if self.entries.len() < self.size {
+            return None;
+        }
+        let lru = match self.recency.back() {
+            Some(index) => self.recency.get(index),
+            None => return None,
+        };
+        let lfu = self.frequency.values().next().unwrap();
+        if self.draw < self.weight_lru {
+            Some(lru.clone())
+        } else {
+            Some(lfu.clone())
+        }
+    }
+}
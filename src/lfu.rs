@@ -1,74 +1,194 @@
 //! Implementation of LFU (Least-Frequently Used) cache algorithm.
 //!
 //! Counts how often an item is needed. Those that are used least often are discarded first.
+//!
+//! Elements with equal frequency are kept in the same list (frequency list), the cache remembers
+//! the minimal frequency, so the element for discarding is found in constant time. Which element of
+//! the frequency list is discarded, is defined by `TieBreak` rule.
 
 use slog;
 
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+/// Rule for choosing the element for discarding among elements with equal frequency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Discard the least recently used element. Used by default.
+    LRU,
+    /// Discard the most recently used element.
+    MRU,
+    /// Discard the element, which entered the frequency list first.
+    ///
+    /// The element enters the next frequency list on every hit, so the rule discards the same
+    /// element as `LRU` does, not the element, which was put into the cache first.
+    FIFO,
+}
+
+// Information about the element in the cache.
+struct Entry {
+    freq: usize,
+    index: usize,
+}
+
 /// Implementation cache, based on a LFU algorithm.
-pub struct LFUCache {
-    cache: Vec<(i32, usize)>,
+pub struct LFUCache<K> {
+    entries: HashMap<K, Entry>,
+    freqs: HashMap<usize, List<K>>,
+    min_freq: usize,
+    tie_break: TieBreak,
     size: usize,
     logger: slog::Logger,
 }
 
-impl LFUCache {
-    /// Create new cache with fix size.
+impl<K: Key> LFUCache<K> {
+    /// Create new cache with fix size and `TieBreak::LRU` rule.
     pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
         let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
         debug!(logger, "Created LFU cache with size: {}", size);
         LFUCache {
-            cache: Vec::with_capacity(size),
+            entries: HashMap::with_capacity(size),
+            freqs: HashMap::new(),
+            min_freq: 0,
+            tie_break: TieBreak::LRU,
             size: size,
             logger: logger,
         }
     }
 
-    /// Checks the cache to find element. If the cache don't have element, checks the cache size
-    /// and removes old element for pushing new element.
-    ///
-    /// Return `true`, if the cache have element and `false` otherwise.
-    pub fn hit(&mut self, val: &i32) -> bool {
-        if let Some(pos) = self.cache.iter().position(|x| &x.0 == val) {
+    /// Set rule for choosing the element for discarding among elements with equal frequency.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        debug!(self.logger, format!("With {:?} tie break.", self.tie_break));
+        self
+    }
+
+    // Put the element into the front of the frequency list, the list is ordered from the last to
+    // the first entered elements.
+    fn link(&mut self, key: K, freq: usize) -> usize {
+        self.freqs
+            .entry(freq)
+            .or_insert_with(|| List::with_capacity(1))
+            .push_front(key)
+    }
+
+    // Remove the element from its frequency list.
+    fn unlink(&mut self, freq: usize, index: usize) -> K {
+        let (key, empty) = {
+            let list = self.freqs.get_mut(&freq).unwrap();
+            (list.remove(index), list.len() == 0)
+        };
+        if empty {
+            self.freqs.remove(&freq);
+        }
+        key
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for LFUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
             debug!(self.logger, "hit";
-                   "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
-            self.cache[pos].1 += 1;
+                   "cache" => format!("{:?}", self.freqs),
+                   "hit" => format!("{:?}", val));
             true
         } else {
-            if self.cache.len() < self.size {
-                self.cache.push((val.clone(), 1));
-            } else {
-                let elem = self.cache.iter()
-                    .position(|x| {
-                        x == self.cache.iter()
-                            .min_by_key(|y| y.1).unwrap()
-                    })
-                    .unwrap();
-                self.cache.remove(elem);
-
-                self.cache.push((val.clone(), 1));
-            }
+            self.insert(val.clone());
             debug!(self.logger, "miss";
-                   "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "cache" => format!("{:?}", self.freqs),
+                   "hit" => format!("{:?}", val));
             false
         }
     }
 
-    /// Run process of checking algorithm.
-    ///
-    /// Return tuple with statistic: `(cache hit, cache miss)`.
-    pub fn run(&mut self, ram: &Vec<Vec<i32>>) -> (i32, i32) {
-        let mut statistic = (0, 0);
-        for batch in ram {
-            for elem in batch {
-                if self.hit(elem) {
-                    statistic.0 += 1;
-                } else {
-                    statistic.1 += 1;
-                }
+    fn reset(&mut self) {
+        self.entries.clear();
+        self.freqs.clear();
+        self.min_freq = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K: Key> Policy<K> for LFUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        let (freq, index) = match self.entries.get(key) {
+            Some(entry) => (entry.freq, entry.index),
+            None => return None,
+        };
+        let key = self.unlink(freq, index);
+        if self.min_freq == freq && !self.freqs.contains_key(&freq) {
+            self.min_freq = freq + 1;
+        }
+        let index = self.link(key.clone(), freq + 1);
+        let entry = self.entries.get_mut(&key).unwrap();
+        entry.freq = freq + 1;
+        entry.index = index;
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.entries.len() < self.size {
+            None
+        } else {
+            let tie_break = self.tie_break;
+            let index = self.freqs.get(&self.min_freq).and_then(|list| match tie_break {
+                TieBreak::LRU | TieBreak::FIFO => list.back(),
+                TieBreak::MRU => list.front(),
+            });
+            index.map(|index| {
+                let min_freq = self.min_freq;
+                let evicted = self.unlink(min_freq, index);
+                self.entries.remove(&evicted);
+                evicted
+            })
+        };
+        let index = self.link(key.clone(), 1);
+        self.entries.insert(key,
+                            Entry {
+                                freq: 1,
+                                index: index,
+                            });
+        self.min_freq = 1;
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(entry) = self.entries.remove(key) {
+            self.unlink(entry.freq, entry.index);
+            if self.min_freq == entry.freq && !self.freqs.contains_key(&entry.freq) {
+                self.min_freq = self.freqs.keys().cloned().min().unwrap_or(0);
             }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn victim(&self) -> Option<K> {
+        if self.entries.len() < self.size {
+            return None;
         }
-        statistic
+        self.freqs.get(&self.min_freq).and_then(|list| {
+            let index = match self.tie_break {
+                TieBreak::LRU | TieBreak::FIFO => list.back(),
+                TieBreak::MRU => list.front(),
+            };
+            index.map(|index| list.get(index).clone())
+        })
     }
 }
@@ -0,0 +1,316 @@
+//! Implementation of LFU-DA (LFU with Dynamic Aging) and GDSF (GreedyDual-Size-Frequency) cache
+//! algorithms.
+//!
+//! LFU never forgets counts of requests, so elements, which were requested often long ago, are
+//! never discarded (cache pollution). LFU-DA discards the element with the least priority
+//! `L + F`, where `F` is the count of requests of the element and `L` is the age of the cache,
+//! which is set to the priority of the last discarded element. So new elements get priorities of
+//! the current age and old elements have to be requested again to stay in the cache.
+//!
+//! GDSF is designed for web proxies, which keep objects of various sizes. The priority of the
+//! object is `L + F * C / S`, where `S` is the size of the object and `C` is the cost of getting
+//! the object (one in this implementation, so the hit rate is optimized), so small objects are
+//! preferred.
+//!
+//! Elements with equal priority are kept in the same list (like frequency lists of `LFUCache`), the
+//! least recently used one is discarded.
+
+use slog;
+
+use std::collections::{BTreeMap, HashMap};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+use priority::Priority;
+
+// Information about the element in the cache.
+struct Entry {
+    freq: usize,
+    size: usize,
+    priority: Priority,
+    index: usize,
+}
+
+// Elements of the cache, ordered by priority, and age of the cache.
+struct Priorities<K> {
+    entries: HashMap<K, Entry>,
+    lists: BTreeMap<Priority, List<K>>,
+    age: f64,
+}
+
+impl<K: Key> Priorities<K> {
+    fn new(size: usize) -> Self {
+        Priorities {
+            entries: HashMap::with_capacity(size),
+            lists: BTreeMap::new(),
+            age: 0.0,
+        }
+    }
+
+    // Priority of the element with the count of requests and the size.
+    fn priority(&self, freq: usize, size: usize) -> Priority {
+        Priority(self.age + freq as f64 / size as f64)
+    }
+
+    // Put the element into the list of the priority.
+    fn link(&mut self, key: K, priority: Priority) -> usize {
+        self.lists
+            .entry(priority)
+            .or_insert_with(|| List::with_capacity(1))
+            .push_front(key)
+    }
+
+    // Remove the element from the list of its priority.
+    fn unlink(&mut self, priority: Priority, index: usize) -> K {
+        let (key, empty) = {
+            let list = self.lists.get_mut(&priority).unwrap();
+            (list.remove(index), list.len() == 0)
+        };
+        if empty {
+            self.lists.remove(&priority);
+        }
+        key
+    }
+
+    // Put new element into the cache.
+    fn put(&mut self, key: K, size: usize) {
+        let priority = self.priority(1, size);
+        let index = self.link(key.clone(), priority);
+        self.entries.insert(key,
+                            Entry {
+                                freq: 1,
+                                size: size,
+                                priority: priority,
+                                index: index,
+                            });
+    }
+
+    // Count the request of the element and update its priority.
+    fn touch(&mut self, key: &K) {
+        let (freq, size, priority, index) = match self.entries.get(key) {
+            Some(entry) => (entry.freq, entry.size, entry.priority, entry.index),
+            None => return,
+        };
+        let key = self.unlink(priority, index);
+        let priority = self.priority(freq + 1, size);
+        let index = self.link(key.clone(), priority);
+        let entry = self.entries.get_mut(&key).unwrap();
+        entry.freq = freq + 1;
+        entry.priority = priority;
+        entry.index = index;
+    }
+
+    // The element with the least priority.
+    fn victim(&self) -> Option<&K> {
+        self.lists.values().next().map(|list| list.get(list.back().unwrap()))
+    }
+
+    // Discard the element with the least priority and set the age to its priority.
+    //
+    // Return the element and its size.
+    fn evict(&mut self) -> Option<(K, usize)> {
+        let (priority, index) = match self.lists.iter().next() {
+            Some((&priority, list)) => (priority, list.back().unwrap()),
+            None => return None,
+        };
+        let key = self.unlink(priority, index);
+        let entry = self.entries.remove(&key).unwrap();
+        self.age = priority.0;
+        Some((key, entry.size))
+    }
+
+    fn remove(&mut self, key: &K) -> Option<usize> {
+        self.entries.remove(key).map(|entry| {
+            self.unlink(entry.priority, entry.index);
+            entry.size
+        })
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.lists.clear();
+        self.age = 0.0;
+    }
+}
+
+/// Implementation cache, based on a LFU-DA algorithm.
+pub struct LFUDACache<K> {
+    priorities: Priorities<K>,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> LFUDACache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created LFU-DA cache with size: {}", size);
+        LFUDACache {
+            priorities: Priorities::new(size),
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Current age of the cache (priority of the last discarded element).
+    pub fn age(&self) -> f64 {
+        self.priorities.age
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for LFUDACache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.priorities.lists),
+                   "age" => self.priorities.age,
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.priorities.lists),
+                   "age" => self.priorities.age,
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.priorities.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.priorities.entries.len()
+    }
+}
+
+impl<K: Key> Policy<K> for LFUDACache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.priorities.entries.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        self.priorities.touch(key);
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.priorities.entries.len() < self.size {
+            None
+        } else {
+            self.priorities.evict().map(|(evicted, _)| evicted)
+        };
+        self.priorities.put(key, 1);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        self.priorities.remove(key).is_some()
+    }
+
+    fn victim(&self) -> Option<K> {
+        if self.priorities.entries.len() < self.size {
+            None
+        } else {
+            self.priorities.victim().cloned()
+        }
+    }
+}
+
+/// Implementation cache, based on a GDSF algorithm.
+///
+/// Size of the cache and sizes of elements are measured in the same units (e.g. bytes), so
+/// capacity and length of the cache are total sizes. By default every element has size one, so
+/// GDSF is the same as LFU-DA. Element, which is bigger than the cache, is not put into the cache.
+///
+/// One new element may force discarding of several elements, so GDSF can't be used as a
+/// key-value cache policy.
+pub struct GDSFCache<K> {
+    priorities: Priorities<K>,
+    size_of: Box<dyn Fn(&K) -> usize>,
+    used: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> GDSFCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created GDSF cache with size: {}", size);
+        GDSFCache {
+            priorities: Priorities::new(size),
+            size_of: Box::new(|_| 1),
+            used: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set function, which returns size of the element.
+    ///
+    /// Sizes must not be zero.
+    pub fn with_size_of(mut self, size_of: Box<dyn Fn(&K) -> usize>) -> Self {
+        self.size_of = size_of;
+        self
+    }
+
+    /// Current age of the cache (priority of the last discarded element).
+    pub fn age(&self) -> f64 {
+        self.priorities.age
+    }
+
+    /// Return `true`, if the cache have element.
+    pub fn contains(&self, key: &K) -> bool {
+        self.priorities.entries.contains_key(key)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for GDSFCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.priorities.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.priorities.lists),
+                   "age" => self.priorities.age,
+                   "hit" => format!("{:?}", val));
+            return true;
+        }
+        let size = (self.size_of)(val);
+        assert!(size > 0, "Size of the element must not be zero");
+        if size <= self.size {
+            while self.used + size > self.size {
+                let (evicted, evicted_size) = self.priorities.evict().unwrap();
+                self.used -= evicted_size;
+                debug!(self.logger, "discard"; "element" => format!("{:?}", evicted));
+            }
+            self.priorities.put(val.clone(), size);
+            self.used += size;
+        }
+        debug!(self.logger, "miss";
+               "cache" => format!("{:?}", self.priorities.lists),
+               "age" => self.priorities.age,
+               "hit" => format!("{:?}", val));
+        false
+    }
+
+    fn reset(&mut self) {
+        self.priorities.clear();
+        self.used = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.used
+    }
+}
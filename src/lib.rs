@@ -6,21 +6,47 @@ extern crate rand;
 #[macro_use] extern crate slog;
 extern crate slog_term;
 
-// use slog::DrainExt;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
 
 pub mod memory;
+pub mod cache;
 pub mod fifo;
 pub mod belady;
 pub mod lru;
+pub mod lruk;
 pub mod lfu;
+pub mod lfuda;
+pub mod wlfu;
+pub mod lrfu;
 pub mod rr;
 pub mod mru;
 pub mod slru;
+pub mod twoq;
+pub mod arc;
+pub mod car;
+pub mod mq;
+pub mod lirs;
+pub mod clock;
+pub mod gclock;
+pub mod nru;
+pub mod clockpro;
+pub mod sieve;
+pub mod s3fifo;
+pub mod tinylfu;
+pub mod lecar;
+pub mod plru;
+pub mod assoc;
+
+mod list;
+mod priority;
 
 /// List of cache algorithms.
 ///
-/// See info about each algorithm in structure of algorithm.
-#[derive(Debug)]
+/// See info about each algorithm in structure of algorithm. Algorithm may be parsed from its name
+/// (e.g. `"LRU".parse::<Algos>()`), the name is the same as `Display` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Algos {
     Belady,
     FIFO,
@@ -29,57 +55,218 @@ pub enum Algos {
     RR,
     MRU,
     PLRU,
+    BitPLRU,
     SLRU,
     TwoQ,
     ARC,
     CAR,
+    CART,
     MQ,
+    LIRS,
+    CLOCK,
+    GCLOCK,
+    NRU,
+    ClockPro,
+    SIEVE,
+    S3FIFO,
+    TinyLFU,
+    WTinyLFU,
+    LRU2,
+    LFUDA,
+    GDSF,
+    WLFU,
+    LRFU,
+    LeCaR,
+}
+
+impl Algos {
+    /// Return `true`, if the algorithm uses random, so its results depend on the seed.
+    pub fn uses_random(&self) -> bool {
+        match *self {
+            Algos::RR | Algos::LeCaR => true,
+            _ => false,
+        }
+    }
 }
 
-// trait CacheAlgorithm {
-//     fn run(&mut self, ram: &Vec<Vec<i32>>) -> (i32, i32);
-// }
-
-// pub struct CacheAlgo<T> {
-//     logger: slog::Logger,
-//     ram: Vec<Vec<i32>>,
-//     cache_algo: Box<T>,
-// }
-
-// impl<T: CacheAlgorithm> CacheAlgo<T> {
-//     pub fn init(algo: Algos, size: usize, ram: Vec<Vec<i32>>, logger: Option<slog::Logger>) -> Self {
-//         let logger = logger.unwrap_or(slog::Logger::root(slog_term::streamer().full().build().fuse(), o!()));
-//         debug!(logger, "Created CacheAlgo with {:?} cache algorithm.", algo);
-
-//         let algo = match algo {
-//             Algos::Belady => belady::BeladyCache::new(size, Some(logger.clone())),
-//             // Algos::FIFO => fifo::FifoCache::new(size, Some(logger.clone())),
-//             Algos::FIFO => unimplemented!(),
-//             Algos::LRU => unimplemented!(),
-//             Algos::MRU => unimplemented!(),
-//             Algos::PLRU => unimplemented!(),
-//             Algos::SLRU => unimplemented!(),
-//             Algos::LFU => unimplemented!(),
-//             Algos::RR => unimplemented!(),
-//             Algos::TwoQ => unimplemented!(),
-//             Algos::ARC => unimplemented!(),
-//             Algos::CAR => unimplemented!(),
-//             Algos::MQ => unimplemented!(),
-//         };
-
-//         CacheAlgo::<T> {
-//             logger: logger,
-//             ram: ram,
-//             cache_algo: Box::new(algo),
-//         }
-//     }
-
-//     pub fn with_ram(mut self, ram: Vec<Vec<i32>>) -> Self {
-//         self.ram = ram;
-//         self
-//     }
-
-//     pub fn run(&mut self) -> (i32, i32) {
-//         self.cache_algo.run(&self.ram)
-//     }
-// }
+impl fmt::Display for Algos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Algos::Belady => "BELADY",
+            Algos::FIFO => "FIFO",
+            Algos::LRU => "LRU",
+            Algos::LFU => "LFU",
+            Algos::RR => "RR",
+            Algos::MRU => "MRU",
+            Algos::PLRU => "PLRU",
+            Algos::BitPLRU => "BPLRU",
+            Algos::SLRU => "SLRU",
+            Algos::TwoQ => "2Q",
+            Algos::ARC => "ARC",
+            Algos::CAR => "CAR",
+            Algos::CART => "CART",
+            Algos::MQ => "MQ",
+            Algos::LIRS => "LIRS",
+            Algos::CLOCK => "CLOCK",
+            Algos::GCLOCK => "GCLOCK",
+            Algos::NRU => "NRU",
+            Algos::ClockPro => "CLOCKPRO",
+            Algos::SIEVE => "SIEVE",
+            Algos::S3FIFO => "S3FIFO",
+            Algos::TinyLFU => "TINYLFU",
+            Algos::WTinyLFU => "WTINYLFU",
+            Algos::LRU2 => "LRU2",
+            Algos::LFUDA => "LFUDA",
+            Algos::GDSF => "GDSF",
+            Algos::WLFU => "WLFU",
+            Algos::LRFU => "LRFU",
+            Algos::LeCaR => "LECAR",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Algos {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BELADY" => Ok(Algos::Belady),
+            "FIFO" => Ok(Algos::FIFO),
+            "LRU" => Ok(Algos::LRU),
+            "LFU" => Ok(Algos::LFU),
+            "RR" => Ok(Algos::RR),
+            "MRU" => Ok(Algos::MRU),
+            "PLRU" => Ok(Algos::PLRU),
+            "BPLRU" => Ok(Algos::BitPLRU),
+            "SLRU" => Ok(Algos::SLRU),
+            "2Q" => Ok(Algos::TwoQ),
+            "ARC" => Ok(Algos::ARC),
+            "CAR" => Ok(Algos::CAR),
+            "CART" => Ok(Algos::CART),
+            "MQ" => Ok(Algos::MQ),
+            "LIRS" => Ok(Algos::LIRS),
+            "CLOCK" => Ok(Algos::CLOCK),
+            "GCLOCK" => Ok(Algos::GCLOCK),
+            "NRU" => Ok(Algos::NRU),
+            "CLOCKPRO" => Ok(Algos::ClockPro),
+            "SIEVE" => Ok(Algos::SIEVE),
+            "S3FIFO" => Ok(Algos::S3FIFO),
+            "TINYLFU" => Ok(Algos::TinyLFU),
+            "WTINYLFU" => Ok(Algos::WTinyLFU),
+            "LRU2" => Ok(Algos::LRU2),
+            "LFUDA" => Ok(Algos::LFUDA),
+            "GDSF" => Ok(Algos::GDSF),
+            "WLFU" => Ok(Algos::WLFU),
+            "LRFU" => Ok(Algos::LRFU),
+            "LECAR" => Ok(Algos::LeCaR),
+            _ => Err(format!("Unknown cache algorithm: {}", s)),
+        }
+    }
+}
+
+/// Create cache with fix size, based on the `algo` algorithm.
+///
+/// Algorithms, which use random, are created with random seed.
+pub fn init<K: Key + 'static>(algo: Algos,
+                              size: usize,
+                              logger: Option<slog::Logger>)
+                              -> Box<dyn CacheAlgorithm<K>> {
+    init_with_seed(algo, size, rand::random(), logger)
+}
+
+/// Create cache with fix size, based on the `algo` algorithm.
+///
+/// Algorithms, which use random, are created with the seed, so the cache discards the same elements
+/// for the same seed.
+pub fn init_with_seed<K: Key + 'static>(algo: Algos,
+                                        size: usize,
+                                        seed: usize,
+                                        logger: Option<slog::Logger>)
+                                        -> Box<dyn CacheAlgorithm<K>> {
+    let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+    debug!(logger, "Init cache with {} cache algorithm.", algo);
+
+    match algo {
+        Algos::Belady => Box::new(belady::BeladyCache::new(size, Some(logger))),
+        Algos::FIFO => Box::new(fifo::FifoCache::new(size, Some(logger))),
+        Algos::LRU => Box::new(lru::LRUCache::new(size, Some(logger))),
+        Algos::LFU => Box::new(lfu::LFUCache::new(size, Some(logger))),
+        Algos::RR => Box::new(rr::RRCache::with_seed(size, seed, Some(logger))),
+        Algos::MRU => Box::new(mru::MRUCache::new(size, Some(logger))),
+        Algos::SLRU => Box::new(slru::SLRUCache::new(size, Some(logger))),
+        Algos::PLRU => Box::new(plru::TreePLRUCache::new(size, Some(logger))),
+        Algos::BitPLRU => Box::new(plru::BitPLRUCache::new(size, Some(logger))),
+        Algos::TwoQ => Box::new(twoq::TwoQCache::new(size, Some(logger))),
+        Algos::ARC => Box::new(arc::ARCCache::new(size, Some(logger))),
+        Algos::CAR => Box::new(car::CARCache::new(size, Some(logger))),
+        Algos::CART => Box::new(car::CARTCache::new(size, Some(logger))),
+        Algos::MQ => Box::new(mq::MQCache::new(size, Some(logger))),
+        Algos::LIRS => Box::new(lirs::LIRSCache::new(size, Some(logger))),
+        Algos::CLOCK => Box::new(clock::ClockCache::new(size, Some(logger))),
+        Algos::GCLOCK => Box::new(gclock::GClockCache::new(size, Some(logger))),
+        Algos::NRU => Box::new(nru::NRUCache::new(size, Some(logger))),
+        Algos::ClockPro => Box::new(clockpro::ClockProCache::new(size, Some(logger))),
+        Algos::SIEVE => Box::new(sieve::SieveCache::new(size, Some(logger))),
+        Algos::S3FIFO => Box::new(s3fifo::S3FifoCache::new(size, Some(logger))),
+        Algos::TinyLFU => Box::new(tinylfu::TinyLFUCache::new(size, Some(logger))),
+        Algos::WTinyLFU => Box::new(tinylfu::WTinyLFUCache::new(size, Some(logger))),
+        Algos::LRU2 => Box::new(lruk::LRUKCache::new(size, Some(logger))),
+        Algos::LFUDA => Box::new(lfuda::LFUDACache::new(size, Some(logger))),
+        Algos::GDSF => Box::new(lfuda::GDSFCache::new(size, Some(logger))),
+        Algos::WLFU => Box::new(wlfu::WLFUCache::new(size, Some(logger))),
+        Algos::LRFU => Box::new(lrfu::LRFUCache::new(size, Some(logger))),
+        Algos::LeCaR => Box::new(lecar::LeCaRCache::with_seed(size, seed, Some(logger))),
+    }
+}
+
+/// Type of elements (keys), which may be stored in the caches.
+///
+/// Implemented for every type, which may be hashed, compared and cloned, e.g. `i32`, `u64` (block
+/// addresses), `String` (object IDs) or tuples of them. `Debug` is required for logging.
+pub trait Key: Hash + Eq + Clone + fmt::Debug {}
+
+impl<T: Hash + Eq + Clone + fmt::Debug> Key for T {}
+
+/// Common interface of all cache algorithms.
+///
+/// Every policy implements this trait, so a policy may be chosen at runtime and used through
+/// `Box<dyn CacheAlgorithm<K>>`. Elements are `i32` by default.
+pub trait CacheAlgorithm<K = i32> {
+    /// Checks the cache to find element. If the cache don't have element, the policy makes room for
+    /// it (if necessary) and puts it into the cache.
+    ///
+    /// Return `true`, if the cache have element and `false` otherwise.
+    fn access(&mut self, val: &K) -> bool;
+
+    /// Remove all elements and all history from the cache.
+    fn reset(&mut self);
+
+    /// Maximum count of elements in the cache.
+    fn capacity(&self) -> usize;
+
+    /// Current count of elements in the cache.
+    fn len(&self) -> usize;
+
+    /// Return `true`, if the cache don't have elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Run process of checking algorithm.
+    ///
+    /// Return tuple with statistic: `(cache hit, cache miss)`.
+    fn run(&mut self, ram: &Vec<Vec<K>>) -> (i32, i32) {
+        let mut statistic = (0, 0);
+        for batch in ram {
+            for elem in batch {
+                if self.access(elem) {
+                    statistic.0 += 1;
+                } else {
+                    statistic.1 += 1;
+                }
+            }
+        }
+        statistic
+    }
+}
@@ -0,0 +1,330 @@
+//! Implementation of LIRS (Low Inter-reference Recency Set) cache algorithm.
+//!
+//! LIRS uses reuse distance (count of other elements, requested between two requests of the
+//! element) instead of recency to choose the element for discarding. Elements with small reuse
+//! distance are LIR elements, they are never discarded. Other elements are HIR elements, only small
+//! part of the cache is given to resident HIR elements.
+//!
+//! The stack S keeps recently requested elements (LIR, resident HIR and non-resident HIR) from the
+//! most to the least recently requested, the bottom of the stack is always LIR element (stack is
+//! pruned after every change). HIR element, which is requested while it is in the stack, has reuse
+//! distance smaller than the least recently requested LIR element, so it becomes LIR and the bottom
+//! LIR element becomes HIR. Resident HIR elements are kept in the queue Q, element for discarding
+//! is taken from the front of the queue.
+
+use slog;
+
+use std::cmp;
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+// Status of the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Lir,
+    Hir,
+    NonResident,
+}
+
+// Information about the element, `queue` is index in Q for resident HIR element and index in the
+// list of non-resident elements for non-resident HIR element.
+struct Entry {
+    state: State,
+    stack: Option<usize>,
+    queue: Option<usize>,
+}
+
+/// Implementation cache, based on a LIRS algorithm.
+///
+/// By default 1% of the cache (at least one element) is given to resident HIR elements. The stack
+/// remembers at most size of the cache non-resident elements, the oldest ones are forgotten.
+pub struct LIRSCache<K> {
+    stack: List<K>,
+    queue: List<K>,
+    non_resident: List<K>,
+    entries: HashMap<K, Entry>,
+    count_lir: usize,
+    size_hir: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> LIRSCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created LIRS cache with size: {}", size);
+        LIRSCache {
+            stack: List::with_capacity(size * 2),
+            queue: List::with_capacity(size),
+            non_resident: List::with_capacity(size),
+            entries: HashMap::with_capacity(size * 2),
+            count_lir: 0,
+            size_hir: size_hir(size, 0.01),
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set part of the cache, which is given to resident HIR elements.
+    ///
+    /// At least one element is HIR and at least one element is LIR (if the cache is greater than
+    /// one element). Panics, if the fraction is not in `0..=1` range.
+    pub fn with_hir(mut self, fraction: f64) -> Self {
+        assert!((0.0..=1.0).contains(&fraction), "HIR fraction must be in 0..=1 range");
+        self.size_hir = size_hir(self.size, fraction);
+        debug!(self.logger, format!("With {} HIR elements.", self.size_hir));
+        self
+    }
+
+    // Put the element on the top of the stack.
+    fn push(&mut self, key: &K) {
+        let index = match self.entries[key].stack {
+            Some(index) => {
+                self.stack.move_to_front(index);
+                index
+            }
+            None => self.stack.push_front(key.clone()),
+        };
+        self.entries.get_mut(key).unwrap().stack = Some(index);
+    }
+
+    // Remove HIR elements from the bottom of the stack.
+    fn prune(&mut self) {
+        while let Some(index) = self.stack.back() {
+            if self.entries[self.stack.get(index)].state == State::Lir {
+                break;
+            }
+            let key = self.stack.pop_back().unwrap();
+            let (state, queue) = {
+                let entry = self.entries.get_mut(&key).unwrap();
+                entry.stack = None;
+                (entry.state, entry.queue)
+            };
+            if state == State::NonResident {
+                self.non_resident.remove(queue.unwrap());
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    // Make the bottom LIR element of the stack resident HIR element.
+    fn demote(&mut self) {
+        self.prune();
+        if let Some(key) = self.stack.pop_back() {
+            let index = self.queue.push_front(key.clone());
+            let entry = self.entries.get_mut(&key).unwrap();
+            entry.state = State::Hir;
+            entry.stack = None;
+            entry.queue = Some(index);
+            self.count_lir -= 1;
+        }
+        self.prune();
+    }
+
+    // Demote LIR element, if there are too many LIR elements.
+    fn balance(&mut self) {
+        if self.count_lir > self.size - self.size_hir {
+            self.demote();
+        }
+    }
+
+    // Discard the oldest element of Q, it is remembered, if it is in the stack.
+    fn evict(&mut self) -> Option<K> {
+        if self.queue.len() == 0 {
+            self.demote();
+        }
+        let evicted = self.queue.pop_back();
+        if let Some(ref key) = evicted {
+            if self.entries[key].stack.is_some() {
+                let index = self.non_resident.push_front(key.clone());
+                let entry = self.entries.get_mut(key).unwrap();
+                entry.state = State::NonResident;
+                entry.queue = Some(index);
+                if self.non_resident.len() > self.size {
+                    self.forget();
+                }
+            } else {
+                self.entries.remove(key);
+            }
+        }
+        evicted
+    }
+
+    // Forget the oldest non-resident element.
+    fn forget(&mut self) {
+        if let Some(key) = self.non_resident.pop_back() {
+            if let Some(entry) = self.entries.remove(&key) {
+                if let Some(index) = entry.stack {
+                    self.stack.remove(index);
+                }
+            }
+        }
+    }
+}
+
+// Count of resident HIR elements for the fraction of the cache.
+fn size_hir(size: usize, fraction: f64) -> usize {
+    cmp::min(cmp::max(1, (size as f64 * fraction) as usize), size.saturating_sub(1))
+}
+
+impl<K: Key> CacheAlgorithm<K> for LIRSCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "stack" => format!("{:?}", self.stack),
+                   "queue" => format!("{:?}", self.queue),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "stack" => format!("{:?}", self.stack),
+                   "queue" => format!("{:?}", self.queue),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.stack.clear();
+        self.queue.clear();
+        self.non_resident.clear();
+        self.entries.clear();
+        self.count_lir = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.count_lir + self.queue.len()
+    }
+}
+
+impl<K: Key> Policy<K> for LIRSCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        match self.entries.get(key) {
+            Some(entry) => entry.state != State::NonResident,
+            None => false,
+        }
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        let (state, stack, queue) = match self.entries.get(key) {
+            Some(entry) => (entry.state, entry.stack, entry.queue),
+            None => return None,
+        };
+        match state {
+            State::Lir => self.push(key),
+            State::Hir if stack.is_some() => {
+                self.queue.remove(queue.unwrap());
+                {
+                    let entry = self.entries.get_mut(key).unwrap();
+                    entry.state = State::Lir;
+                    entry.queue = None;
+                }
+                self.count_lir += 1;
+                self.push(key);
+                self.balance();
+            }
+            State::Hir => {
+                self.queue.remove(queue.unwrap());
+                let index = self.queue.push_front(key.clone());
+                self.entries.get_mut(key).unwrap().queue = Some(index);
+                self.push(key);
+            }
+            State::NonResident => {}
+        }
+        self.prune();
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.len() < self.size {
+            None
+        } else {
+            self.evict()
+        };
+        let non_resident = self.entries.get(&key).map(|entry| entry.queue);
+        match non_resident {
+            Some(index) => {
+                // The element is in the stack, so its reuse distance is small.
+                self.non_resident.remove(index.unwrap());
+                {
+                    let entry = self.entries.get_mut(&key).unwrap();
+                    entry.state = State::Lir;
+                    entry.queue = None;
+                }
+                self.count_lir += 1;
+                self.push(&key);
+                self.balance();
+            }
+            None => {
+                let state = if self.count_lir < self.size - self.size_hir {
+                    self.count_lir += 1;
+                    State::Lir
+                } else {
+                    State::Hir
+                };
+                let queue = if state == State::Hir {
+                    Some(self.queue.push_front(key.clone()))
+                } else {
+                    None
+                };
+                self.entries.insert(key.clone(),
+                                    Entry {
+                                        state: state,
+                                        stack: None,
+                                        queue: queue,
+                                    });
+                self.push(&key);
+            }
+        }
+        self.prune();
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        let (state, stack, queue) = match self.entries.get(key) {
+            Some(entry) => (entry.state, entry.stack, entry.queue),
+            None => return false,
+        };
+        match state {
+            State::Lir => self.count_lir -= 1,
+            State::Hir => {
+                self.queue.remove(queue.unwrap());
+            }
+            State::NonResident => return false,
+        }
+        if let Some(index) = stack {
+            self.stack.remove(index);
+        }
+        self.entries.remove(key);
+        self.prune();
+        true
+    }
+    fn victim(&self) -> Option<K> {
+        if self.len() < self.size {
+            return None;
+        }
+        if let Some(index) = self.queue.back() {
+            return Some(self.queue.get(index).clone());
+        }
+        // Q is empty, so the bottom LIR element of the stack is demoted and discarded.
+        let mut index = self.stack.back();
+        while let Some(current) = index {
+            let key = self.stack.get(current);
+            if self.entries[key].state == State::Lir {
+                return Some(key.clone());
+            }
+            index = self.stack.prev(current);
+        }
+        None
+    }
+}
@@ -0,0 +1,228 @@
+//! Implementation of doubly linked list.
+//!
+//! Nodes of the list are stored in the vector (slab) and linked by indexes. Pushing returns index of
+//! the node, so the element may be moved or removed by the index in constant time. Indexes of
+//! removed nodes are reused.
+
+use std::fmt;
+
+struct Node<T> {
+    value: Option<T>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// Doubly linked list with access to nodes by indexes.
+pub struct List<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+}
+
+impl<T> List<T> {
+    /// Create new list with capacity for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        List {
+            nodes: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    /// Count of elements in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Push element to the front of the list.
+    ///
+    /// Return index of the element.
+    pub fn push_front(&mut self, value: T) -> usize {
+        let index = self.alloc(value);
+        self.link_front(index);
+        index
+    }
+
+    /// Push element to the back of the list.
+    ///
+    /// Return index of the element.
+    pub fn push_back(&mut self, value: T) -> usize {
+        let index = self.alloc(value);
+        self.link_back(index);
+        index
+    }
+
+    /// Insert element before the element by the index.
+    ///
+    /// Return index of the inserted element.
+    pub fn insert_before(&mut self, next: usize, value: T) -> usize {
+        let index = self.alloc(value);
+        let prev = self.nodes[next].prev;
+        self.nodes[index].prev = prev;
+        self.nodes[index].next = Some(next);
+        self.nodes[next].prev = Some(index);
+        match prev {
+            Some(prev) => self.nodes[prev].next = Some(index),
+            None => self.head = Some(index),
+        }
+        index
+    }
+
+    /// Index of the front element.
+    pub fn front(&self) -> Option<usize> {
+        self.head
+    }
+
+    /// Index of the back element.
+    pub fn back(&self) -> Option<usize> {
+        self.tail
+    }
+
+    /// Index of the element after the element by the index (in direction to the back).
+    pub fn next(&self, index: usize) -> Option<usize> {
+        self.nodes[index].next
+    }
+
+    /// Index of the element before the element by the index (in direction to the front).
+    pub fn prev(&self, index: usize) -> Option<usize> {
+        self.nodes[index].prev
+    }
+
+    /// Element by the index.
+    ///
+    /// Panics, if the index is not used.
+    pub fn get(&self, index: usize) -> &T {
+        self.nodes[index].value.as_ref().unwrap()
+    }
+
+    /// Mutable element by the index.
+    ///
+    /// Panics, if the index is not used.
+    pub fn get_mut(&mut self, index: usize) -> &mut T {
+        self.nodes[index].value.as_mut().unwrap()
+    }
+
+    /// Remove element from the front of the list.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|index| self.remove(index))
+    }
+
+    /// Remove element from the back of the list.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|index| self.remove(index))
+    }
+
+    /// Remove element by the index.
+    ///
+    /// Panics, if the index is not used.
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.nodes[index].value.take().unwrap();
+        self.unlink(index);
+        self.free.push(index);
+        self.len -= 1;
+        value
+    }
+
+    /// Move element by the index to the front of the list.
+    pub fn move_to_front(&mut self, index: usize) {
+        if self.head != Some(index) {
+            self.unlink(index);
+            self.link_front(index);
+        }
+    }
+
+    /// Remove all elements.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.head = None;
+        self.tail = None;
+        self.len = 0;
+    }
+
+    /// Iterate over elements from the front to the back.
+    pub fn iter<'a>(&'a self) -> Iter<'a, T> {
+        Iter {
+            list: self,
+            next: self.head,
+        }
+    }
+
+    // Store element in the free node.
+    fn alloc(&mut self, value: T) -> usize {
+        let node = Node {
+            value: Some(value),
+            prev: None,
+            next: None,
+        };
+        self.len += 1;
+        if let Some(index) = self.free.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn link_front(&mut self, index: usize) {
+        self.nodes[index].prev = None;
+        self.nodes[index].next = self.head;
+        match self.head {
+            Some(head) => self.nodes[head].prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+
+    fn link_back(&mut self, index: usize) {
+        self.nodes[index].prev = self.tail;
+        self.nodes[index].next = None;
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let prev = self.nodes[index].prev;
+        let next = self.nodes[index].next;
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over elements of the list.
+pub struct Iter<'a, T: 'a> {
+    list: &'a List<T>,
+    next: Option<usize>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|index| {
+            let node = &self.list.nodes[index];
+            self.next = node.next;
+            node.value.as_ref().unwrap()
+        })
+    }
+}
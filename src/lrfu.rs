@@ -0,0 +1,172 @@
+//! Implementation of LRFU (Least Recently/Frequently Used) cache algorithm.
+//!
+//! Every request of the element adds to its CRF (Combined Recency and Frequency) value, which is
+//! decayed exponentially with time: CRF of the element at time `t` is the sum of `(1/2)^(λ(t - ti))`
+//! over times `ti` of its requests. The element with the least CRF is discarded. The parameter `λ`
+//! (in `0..=1` range) moves the algorithm from LFU (`λ = 0`, every request adds one forever) to LRU
+//! (`λ = 1`, the last request outweighs all previous ones).
+//!
+//! All CRF values decay with the same speed, so the order of elements changes only on requests. The
+//! element is ordered by `log2(CRF) + λt`, where `CRF` is the value at time `t` of the last request.
+//!
+//! Time is measured in requests. CRF values of discarded elements are forgotten.
+
+use slog;
+
+use std::collections::{BTreeMap, HashMap};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use priority::Priority;
+
+// Information about the element in the cache.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    crf: f64,
+    last: u64,
+}
+
+/// Implementation cache, based on a LRFU algorithm.
+///
+/// By default `λ` is `0.01`. Elements with equal CRF are ordered by time of the last request.
+pub struct LRFUCache<K> {
+    entries: HashMap<K, Entry>,
+    order: BTreeMap<(Priority, u64), K>,
+    lambda: f64,
+    time: u64,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> LRFUCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created LRFU cache with size: {}", size);
+        LRFUCache {
+            entries: HashMap::with_capacity(size),
+            order: BTreeMap::new(),
+            lambda: 0.01,
+            time: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set the parameter `λ`.
+    ///
+    /// Panics, if `λ` is not in `0..=1` range.
+    pub fn with_lambda(mut self, lambda: f64) -> Self {
+        assert!((0.0..=1.0).contains(&lambda), "Lambda must be in 0..=1 range");
+        self.lambda = lambda;
+        debug!(self.logger, format!("With lambda: {}", self.lambda));
+        self
+    }
+
+    /// Current CRF value of the element, if the cache have element.
+    pub fn crf(&self, key: &K) -> Option<f64> {
+        self.entries.get(key).map(|entry| entry.crf * self.decay(self.time - entry.last))
+    }
+
+    // Weight of the request, which was `age` requests ago.
+    fn decay(&self, age: u64) -> f64 {
+        0.5f64.powf(self.lambda * age as f64)
+    }
+
+    // Key of the element in the order of discarding.
+    fn order_key(&self, entry: &Entry) -> (Priority, u64) {
+        (Priority(entry.crf.log2() + self.lambda * entry.last as f64), entry.last)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for LRFUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.order),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.order),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.time = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K: Key> Policy<K> for LRFUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        let entry = match self.entries.get(key) {
+            Some(&entry) => entry,
+            None => return None,
+        };
+        self.time += 1;
+        self.order.remove(&self.order_key(&entry));
+        let entry = Entry {
+            crf: 1.0 + entry.crf * self.decay(self.time - entry.last),
+            last: self.time,
+        };
+        self.order.insert(self.order_key(&entry), key.clone());
+        self.entries.insert(key.clone(), entry);
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.time += 1;
+        let evicted = if self.entries.len() < self.size {
+            None
+        } else {
+            let first = self.order.keys().next().cloned();
+            first.map(|first| {
+                let evicted = self.order.remove(&first).unwrap();
+                self.entries.remove(&evicted);
+                evicted
+            })
+        };
+        let entry = Entry {
+            crf: 1.0,
+            last: self.time,
+        };
+        self.order.insert(self.order_key(&entry), key.clone());
+        self.entries.insert(key, entry);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&self.order_key(&entry));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn victim(&self) -> Option<K> {
+        if self.entries.len() < self.size {
+            None
+        } else {
+            self.order.values().next().cloned()
+        }
+    }
+}
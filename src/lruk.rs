@@ -0,0 +1,260 @@
+//! Implementation of LRU-K cache algorithm.
+//!
+//! LRU-K remembers times of K last requests of every element and discards the element, whose K-th
+//! last request was the earliest (the element with the greatest backward K-distance). Elements,
+//! which were requested less than K times, have infinite distance and are discarded first, among
+//! them the least recently used one is discarded. LRU-1 is the same as LRU.
+//!
+//! Requests, which follow each other in a short time (e.g. requests of one transaction), are
+//! correlated and are counted as one request: the request during the correlated reference period
+//! (CRP) after the previous request doesn't change the history, and the element can't be discarded
+//! during the period. The history of discarded element is retained during the retained information
+//! period (RIP), so the element, which is requested again, is not treated as a new one.
+//!
+//! Time is measured in requests.
+
+use slog;
+
+use std::collections::{BTreeMap, HashMap};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+
+// Times of requests of the element.
+#[derive(Debug, Clone)]
+struct History {
+    // Times of K last uncorrelated requests from the last one, zero is infinite past.
+    refs: Vec<u64>,
+    // Time of the last request.
+    last: u64,
+}
+
+/// Implementation cache, based on a LRU-K algorithm.
+///
+/// By default K is 2, the correlated reference period is zero and the retained information period
+/// is ten sizes of the cache. Elements of the cache are ordered by time of the K-th last request
+/// and time of the last request, so the element for discarding is the first one outside of the
+/// correlated reference period.
+pub struct LRUKCache<K> {
+    resident: HashMap<K, History>,
+    order: BTreeMap<(u64, u64), K>,
+    retained: HashMap<K, History>,
+    retained_order: BTreeMap<u64, K>,
+    k: usize,
+    correlated_period: u64,
+    retained_period: u64,
+    time: u64,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> LRUKCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created LRU-K cache with size: {}", size);
+        LRUKCache {
+            resident: HashMap::with_capacity(size),
+            order: BTreeMap::new(),
+            retained: HashMap::new(),
+            retained_order: BTreeMap::new(),
+            k: 2,
+            correlated_period: 0,
+            retained_period: 10 * size as u64,
+            time: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set count of remembered requests of every element.
+    ///
+    /// Panics, if K is zero.
+    pub fn with_k(mut self, k: usize) -> Self {
+        assert!(k > 0, "K must not be zero");
+        self.k = k;
+        debug!(self.logger, format!("With K: {}", self.k));
+        self
+    }
+
+    /// Set count of requests after the request, during which requests of the element are
+    /// correlated.
+    pub fn with_correlated_period(mut self, period: u64) -> Self {
+        self.correlated_period = period;
+        debug!(self.logger, format!("With correlated reference period: {}", period));
+        self
+    }
+
+    /// Set count of requests after the last request, during which history of discarded element is
+    /// retained.
+    pub fn with_retained_period(mut self, period: u64) -> Self {
+        self.retained_period = period;
+        debug!(self.logger, format!("With retained information period: {}", period));
+        self
+    }
+
+    // Key of the element in the order of discarding.
+    fn order_key(&self, history: &History) -> (u64, u64) {
+        (history.refs[self.k - 1], history.last)
+    }
+
+    // Count the request and forget histories, which are retained too long.
+    fn tick(&mut self) {
+        self.time += 1;
+        if self.time <= self.retained_period {
+            return;
+        }
+        let expired = self.time - self.retained_period;
+        while let Some(last) = self.retained_order.keys().next().cloned() {
+            if last >= expired {
+                break;
+            }
+            let key = self.retained_order.remove(&last).unwrap();
+            self.retained.remove(&key);
+        }
+    }
+
+    // Find the element for discarding at the time.
+    //
+    // If all elements are in the correlated reference period, the first element of the order is
+    // discarded.
+    fn find_victim(&self, time: u64) -> Option<&K> {
+        let period = self.correlated_period;
+        self.order
+            .iter()
+            .find(|&(&(_, last), _)| time - last > period)
+            .or_else(|| self.order.iter().next())
+            .map(|(_, key)| key)
+    }
+
+    // Discard the element and retain its history.
+    fn evict(&mut self) -> Option<K> {
+        let key = match self.find_victim(self.time) {
+            Some(key) => key.clone(),
+            None => return None,
+        };
+        let history = self.resident.remove(&key).unwrap();
+        self.order.remove(&self.order_key(&history));
+        if self.retained_period > 0 {
+            self.retained_order.insert(history.last, key.clone());
+            self.retained.insert(key.clone(), history);
+        }
+        Some(key)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for LRUKCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.order),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.order),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.resident.clear();
+        self.order.clear();
+        self.retained.clear();
+        self.retained_order.clear();
+        self.time = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.resident.len()
+    }
+}
+
+impl<K: Key> Policy<K> for LRUKCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.resident.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        let mut history = match self.resident.get(key) {
+            Some(history) => history.clone(),
+            None => return None,
+        };
+        self.tick();
+        self.order.remove(&self.order_key(&history));
+        let time = self.time;
+        if time - history.last > self.correlated_period {
+            // Close the correlated period of the previous request, so all previous requests are
+            // shifted by its length.
+            let correlated = history.last - history.refs[0];
+            for i in (1..self.k).rev() {
+                history.refs[i] = if history.refs[i - 1] == 0 {
+                    0
+                } else {
+                    history.refs[i - 1] + correlated
+                };
+            }
+            history.refs[0] = time;
+        }
+        history.last = time;
+        self.order.insert(self.order_key(&history), key.clone());
+        self.resident.insert(key.clone(), history);
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.tick();
+        let evicted = if self.resident.len() < self.size {
+            None
+        } else {
+            self.evict()
+        };
+        let time = self.time;
+        let history = match self.retained.remove(&key) {
+            Some(mut history) => {
+                self.retained_order.remove(&history.last);
+                for i in (1..self.k).rev() {
+                    history.refs[i] = history.refs[i - 1];
+                }
+                history.refs[0] = time;
+                history.last = time;
+                history
+            }
+            None => {
+                let mut refs = vec![0; self.k];
+                refs[0] = time;
+                History {
+                    refs: refs,
+                    last: time,
+                }
+            }
+        };
+        self.order.insert(self.order_key(&history), key.clone());
+        self.resident.insert(key, history);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(history) = self.resident.remove(key) {
+            self.order.remove(&self.order_key(&history));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn victim(&self) -> Option<K> {
+        if self.resident.len() < self.size {
+            None
+        } else {
+            self.find_victim(self.time + 1).cloned()
+        }
+    }
+}
@@ -3,29 +3,27 @@
 //! TODO Write documentation.
 
 extern crate cache_algos;
+extern crate rand;
 #[macro_use]
 extern crate slog;
 extern crate slog_term;
 extern crate slog_envlogger;
 
 use slog::DrainExt;
+use rand::{Rng, SeedableRng, StdRng};
 
 use std::env;
+use std::io::{self, Write};
 
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::assoc::{Indexing, SetAssociativeCache};
 use cache_algos::memory::RamBuilder;
-use cache_algos::fifo::FifoCache;
-use cache_algos::belady::BeladyCache;
-use cache_algos::lru::LRUCache;
-use cache_algos::lfu::LFUCache;
-use cache_algos::rr::RRCache;
-use cache_algos::mru::MRUCache;
-use cache_algos::slru::SLRUCache;
 
 struct Options {
     file: bool,
     builder: bool,
     path_file: Option<String>,
-    algo: Option<String>,
+    algo: Option<Algos>,
     count_of_batches: Option<usize>,
     size_of_batch: Option<usize>,
     all_random: bool,
@@ -33,6 +31,10 @@ struct Options {
     low_range: Option<i32>,
     high_range: Option<i32>,
     cache_size: Option<usize>,
+    seed: Option<usize>,
+    ways: Option<usize>,
+    line_size: Option<usize>,
+    xor: bool,
 }
 
 fn main() {
@@ -52,9 +54,14 @@ fn main() {
         low_range: None,
         high_range: None,
         cache_size: None,
+        seed: None,
+        ways: None,
+        line_size: None,
+        xor: false,
     };
     let usage = format!("Usage: {} -f PATH | -b COUNT_OF_BATCHES | -s SIZE_OF_BATCH \
-                 [-L | -R [-r RANDOM_LOW RANDOM_HIGH]] [-S SIZE_CACHE] ALGO\n\
+                 [-L | -R [-r RANDOM_LOW RANDOM_HIGH]] [-S SIZE_CACHE] [--seed SEED] \
+                 [-W WAYS [--line LINE_SIZE] [--xor]] ALGO\n\
                  \n\
                  Create RAM from FILE:\n\
                  -f PATH                Create RAM from file\n\
@@ -66,9 +73,19 @@ fn main() {
                  -L                     Set random for one batch (range random is size of batch)\n\
                  -r                     Set range limit of random\n\
                  -S                     Set size of cache\n\
+                 --seed SEED            Set seed of random (random RAM, RR and LECAR, also in sets\n\
+                 \x20                      of -W cache), the seed is printed to stderr\n\
+                 \n\
+                 Set-associative cache (elements are addresses of bytes):\n\
+                 -W WAYS                Set associativity, every set is managed by ALGO, size of\n\
+                 \x20                      cache is measured in bytes\n\
+                 --line LINE_SIZE       Set size of cache line in bytes (1 by default)\n\
+                 --xor                  Set XOR indexing of sets\n\
                  \n\
                  Algorithms (ALGO variable):\n\
-                 BELADY, FIFO, LRU, LFU, RR, MRU, SLRU", env::args().nth(0).unwrap());
+                 BELADY, FIFO, LRU, LFU, RR, MRU, SLRU, 2Q, ARC, CAR, CART, MQ, LIRS, PLRU, BPLRU,\n\
+                 CLOCK, GCLOCK, NRU, CLOCKPRO, SIEVE, S3FIFO, TINYLFU, WTINYLFU,\n\
+                 LRU2, LFUDA, GDSF, WLFU, LRFU, LECAR", env::args().nth(0).unwrap());
     if env::args().len() < 2 {
         println!("{}", usage);
         return
@@ -139,23 +156,45 @@ fn main() {
                         panic!("Cache size must be greater than 1!");
                     }
                 },
-                a @ "BELADY" | a @ "FIFO" | a @ "LRU" | a @ "LFU" | a @ "RR" | a @ "MRU" | a @ "SLRU" => {
-                    if !(options.file || options.builder) {
-                        println!("{}", usage);
-                        return
+                "--seed" => {
+                    options.seed = env::args().nth(i + 1).map(|x| x.parse().unwrap());
+                    debug!(logger, format!("Set seed: {}", options.seed.unwrap()));
+                },
+                "-W" => {
+                    options.ways = env::args().nth(i + 1).map(|x| x.parse().unwrap());
+                    debug!(logger, format!("Set associativity: {}", options.ways.unwrap()));
+                },
+                "--line" => {
+                    options.line_size = env::args().nth(i + 1).map(|x| x.parse().unwrap());
+                    debug!(logger, format!("Set line size: {}", options.line_size.unwrap()));
+                },
+                "--xor" => {
+                    options.xor = true;
+                    debug!(logger, "Set XOR indexing");
+                },
+                a => {
+                    if let Ok(algo) = a.parse::<Algos>() {
+                        if !(options.file || options.builder) {
+                            println!("{}", usage);
+                            return
+                        }
+                        options.algo = Some(algo);
+                        debug!(logger, format!("Algo is {}", algo));
                     }
-                    options.algo = Some(a.to_string());
-                    debug!(logger, format!("Algo is {}", options.algo.clone().unwrap()));
                 },
-                _ => {},
             }
         }
     }
-    if options.algo.clone().is_none() || !(options.builder || options.file) {
+    if options.algo.is_none() || !(options.builder || options.file) {
         println!("{}", usage);
         return
     }
 
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut seeds = StdRng::from_seed(&[seed][..]);
+    let algo = options.algo.unwrap();
+
+    // Bytes of the file and generated elements are simulated as `i32` keys.
     let mut ram: Vec<Vec<i32>> = Vec::new();
 
     if options.builder {
@@ -176,51 +215,29 @@ fn main() {
             ram_builder = ram_builder.with_range_random(options.low_range.unwrap(),
                                                         options.high_range.unwrap());
         }
-        ram = ram_builder.build();
+        ram = ram_builder.with_seed(seeds.gen()).build();
     }
     if options.file {
         ram = RamBuilder::from_file(Some(logger.clone()), &options.path_file.unwrap());
     }
 
-    match options.algo.unwrap().as_str() {
-        "BELADY" => {
-            let mut belady_cache = BeladyCache::new(options.cache_size.unwrap_or(100), Some(logger.clone()));
-            let result = belady_cache.run(&ram);
-            println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
-        },
-        "FIFO" => {
-            let mut cache = FifoCache::new(options.cache_size.unwrap_or(100), Some(logger.clone()));
-            let result = cache.run(&ram);
-            println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
-        }
-        "LRU" => {
-            let mut cache = LRUCache::new(options.cache_size.unwrap_or(100), Some(logger.clone()));
-            let result = cache.run(&ram);
-            println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
-        }
-        "LFU" => {
-            let mut cache = LFUCache::new(options.cache_size.unwrap_or(100), Some(logger.clone()));
-            let result = cache.run(&ram);
-            println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
-        }
-        "RR" => {
-            let mut cache = RRCache::new(options.cache_size.unwrap_or(100), Some(logger.clone()));
-            let result = cache.run(&ram);
-            println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
-        }
-        "MRU" => {
-            let mut cache = MRUCache::new(options.cache_size.unwrap_or(100), Some(logger.clone()));
-            let result = cache.run(&ram);
-            println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
-        }
-        "SLRU" => {
-            let mut cache = SLRUCache::new(options.cache_size.unwrap_or(100), Some(logger.clone()));
-            let result = cache.run(&ram);
-            println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
-        }
-        _ => {
-            println!("{}", usage);
-            return
+    let size = options.cache_size.unwrap_or(100);
+    let mut cache: Box<dyn CacheAlgorithm<i32>> = match options.ways {
+        Some(ways) => {
+            let indexing = if options.xor { Indexing::XOR } else { Indexing::Modulo };
+            Box::new(SetAssociativeCache::with_seed(algo,
+                                                    size,
+                                                    options.line_size.unwrap_or(1),
+                                                    ways,
+                                                    seeds.gen(),
+                                                    Some(logger.clone()))
+                .with_indexing(indexing))
         }
+        None => cache_algos::init_with_seed(algo, size, seeds.gen(), Some(logger.clone())),
+    };
+    let result = cache.run(&ram);
+    println!("{:.3}", result.0 as f32 / (result.0 + result.1) as f32);
+    if (options.builder && (options.all_random || options.local_random)) || algo.uses_random() {
+        writeln!(io::stderr(), "seed: {}", seed).unwrap();
     }
 }
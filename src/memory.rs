@@ -1,13 +1,18 @@
 //! Implementation of RAM.
 //!
 //! See info about `RamBuilder` structure.
+//!
+//! Generated RAM has `i32` elements, RAM of other key types (e.g. `u64` block addresses or
+//! `String` object IDs) is read from files by `RamBuilder::from_file` and `RamBuilder::from_lines`.
 
-use rand;
+use rand::{self, SeedableRng, StdRng};
 use rand::distributions::{Range, IndependentSample};
 use slog;
 
+use std::fmt;
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
 
 /// Structure for creating Vec of batches of Vec<i32>.
 ///
@@ -25,6 +30,7 @@ pub struct RamBuilder {
     random_local: bool,
     low: i32,
     high: i32,
+    seed: Option<usize>,
     logger: slog::Logger,
 }
 
@@ -38,14 +44,15 @@ impl RamBuilder {
             random_local: false,
             low: 0,
             high: 100,
+            seed: None,
             logger: logger.unwrap_or(slog::Logger::root(slog::Discard, o!())),
         }
     }
 
     /// Set RAM from the file.
     ///
-    /// Get bytes from the file (element is 8-bit digit).
-    pub fn from_file(logger: Option<slog::Logger>, path: &str) -> Vec<Vec<i32>> {
+    /// Get bytes from the file (element is 8-bit digit), bytes are converted to `K`.
+    pub fn from_file<K: From<u8>>(logger: Option<slog::Logger>, path: &str) -> Vec<Vec<K>> {
         let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
         let mut file = File::open(path).unwrap();
         let mut buf: Vec<u8> = Vec::new();
@@ -54,10 +61,32 @@ impl RamBuilder {
 
         // let mut ram = Vec::with_capacity(self.count_batches);
         vec![buf.into_iter()
-                 .map(|x| x as i32)
+                 .map(K::from)
                  .collect()]
     }
 
+    /// Set RAM from the text file.
+    ///
+    /// Every non-empty line of the file is one element, parsed as `K` (e.g. `u64` block addresses
+    /// or `String` object IDs).
+    pub fn from_lines<K>(logger: Option<slog::Logger>, path: &str) -> Vec<Vec<K>>
+        where K: FromStr,
+              K::Err: fmt::Debug
+    {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        let file = File::open(path).unwrap();
+        let batch: Vec<K> = BufReader::new(file)
+            .lines()
+            .map(|line| line.unwrap())
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.trim().parse().unwrap())
+            .collect();
+        let size = batch.len();
+        debug!(logger, "file({}): {} elements", path, size);
+
+        vec![batch]
+    }
+
     /// Set count of batches.
     pub fn with_count_batches(mut self, c_batches: usize) -> Self {
         self.count_batches = c_batches;
@@ -119,11 +148,20 @@ impl RamBuilder {
         self
     }
 
-    /// Create batches of elements.
+    /// Set seed of random, so the same random elements are generated on every build.
+    ///
+    /// Random seed is used by default.
+    pub fn with_seed(mut self, seed: usize) -> Self {
+        self.seed = Some(seed);
+        debug!(self.logger, format!("With seed {}.", seed));
+        self
+    }
+
+    /// Create batches of `i32` elements.
     pub fn build(self) -> Vec<Vec<i32>> {
         let mut ram = Vec::with_capacity(self.count_batches);
         if self.random || self.random_local {
-            let mut rng = rand::thread_rng();
+            let mut rng = StdRng::from_seed(&[self.seed.unwrap_or_else(rand::random)][..]);
             let between = Range::new(self.low, self.high);
             for _ in 0..self.count_batches {
                 let mut batch = Vec::with_capacity(self.size_batch);
@@ -0,0 +1,231 @@
+//! Implementation of MQ (Multi-Queue) cache algorithm.
+//!
+//! MQ was designed for second level buffer caches, where requests are already filtered by the first
+//! level cache, so recency of requests is less important than their frequency. Elements are kept in
+//! several LRU queues, element with frequency `f` is kept in queue `log2(f)`. Element, which was
+//! not requested during its life time, is demoted to the lower queue, so elements, which were
+//! requested frequently long time ago, are discarded at last. Element for discarding is taken from
+//! the LRU end of the lowest not empty queue.
+//!
+//! Frequencies of discarded elements are remembered in the history buffer Qout, so element, which
+//! is requested again, is put into the cache with its previous frequency.
+
+use slog;
+
+use std::cmp;
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+// Information about the element in the cache.
+struct Entry {
+    queue: usize,
+    index: usize,
+    freq: usize,
+    expire: u64,
+}
+
+/// Implementation cache, based on a MQ algorithm.
+///
+/// By default the cache has 8 queues, life time of elements is equal to size of the cache and Qout
+/// remembers 4 sizes of the cache. Life time is measured in requests.
+pub struct MQCache<K> {
+    queues: Vec<List<K>>,
+    entries: HashMap<K, Entry>,
+    history: List<(K, usize)>,
+    history_map: HashMap<K, usize>,
+    history_size: usize,
+    life_time: u64,
+    time: u64,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> MQCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created MQ cache with size: {}", size);
+        MQCache {
+            queues: (0..8).map(|_| List::with_capacity(size)).collect(),
+            entries: HashMap::with_capacity(size),
+            history: List::with_capacity(size * 4),
+            history_map: HashMap::with_capacity(size * 4),
+            history_size: size * 4,
+            life_time: size as u64,
+            time: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set count of LRU queues.
+    ///
+    /// Panics, if the count is zero.
+    pub fn with_queues(mut self, count: usize) -> Self {
+        assert!(count > 0, "MQ needs at least one queue");
+        self.queues = (0..count).map(|_| List::with_capacity(self.size)).collect();
+        debug!(self.logger, format!("With {} queues.", count));
+        self
+    }
+
+    /// Set life time of elements in count of requests.
+    pub fn with_life_time(mut self, life_time: u64) -> Self {
+        self.life_time = life_time;
+        debug!(self.logger, format!("With life time: {}", self.life_time));
+        self
+    }
+
+    /// Set count of elements, remembered in Qout.
+    pub fn with_history(mut self, count: usize) -> Self {
+        self.history_size = count;
+        debug!(self.logger, format!("With history size: {}", self.history_size));
+        self
+    }
+
+    // Number of the queue for the frequency (`log2(freq)`, but not greater than the last queue).
+    fn queue_num(&self, freq: usize) -> usize {
+        let log2 = (0usize.leading_zeros() - freq.leading_zeros()) as usize - 1;
+        cmp::min(log2, self.queues.len() - 1)
+    }
+
+    // Put the element to the MRU end of the queue by its frequency.
+    fn put(&mut self, key: K, freq: usize) {
+        let queue = self.queue_num(freq);
+        let index = self.queues[queue].push_front(key.clone());
+        self.entries.insert(key,
+                            Entry {
+                                queue: queue,
+                                index: index,
+                                freq: freq,
+                                expire: self.time + self.life_time,
+                            });
+    }
+
+    // Demote LRU elements of queues, which were not requested during their life time.
+    fn adjust(&mut self) {
+        for queue in 1..self.queues.len() {
+            let expired = match self.queues[queue].back() {
+                Some(index) => {
+                    let key = self.queues[queue].get(index);
+                    self.entries[key].expire < self.time
+                }
+                None => false,
+            };
+            if expired {
+                let key = self.queues[queue].pop_back().unwrap();
+                let index = self.queues[queue - 1].push_front(key.clone());
+                let entry = self.entries.get_mut(&key).unwrap();
+                entry.queue = queue - 1;
+                entry.index = index;
+                entry.expire = self.time + self.life_time;
+            }
+        }
+    }
+
+    // Discard the LRU element of the lowest not empty queue and remember it in Qout.
+    fn evict(&mut self) -> Option<K> {
+        let evicted = self.queues.iter_mut().filter_map(|queue| queue.pop_back()).next();
+        if let Some(ref key) = evicted {
+            let entry = self.entries.remove(key).unwrap();
+            if self.history_size > 0 {
+                if self.history.len() >= self.history_size {
+                    if let Some((forgotten, _)) = self.history.pop_back() {
+                        self.history_map.remove(&forgotten);
+                    }
+                }
+                let index = self.history.push_front((key.clone(), entry.freq));
+                self.history_map.insert(key.clone(), index);
+            }
+        }
+        evicted
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for MQCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.queues),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.queues),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        for queue in self.queues.iter_mut() {
+            queue.clear();
+        }
+        self.entries.clear();
+        self.history.clear();
+        self.history_map.clear();
+        self.time = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K: Key> Policy<K> for MQCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(entry) = self.entries.remove(key) {
+            self.time += 1;
+            let key = self.queues[entry.queue].remove(entry.index);
+            self.put(key, entry.freq + 1);
+            self.adjust();
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.time += 1;
+        let freq = match self.history_map.remove(&key) {
+            Some(index) => self.history.remove(index).1 + 1,
+            None => 1,
+        };
+        let evicted = if self.entries.len() < self.size {
+            None
+        } else {
+            self.evict()
+        };
+        self.put(key, freq);
+        self.adjust();
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(entry) = self.entries.remove(key) {
+            self.queues[entry.queue].remove(entry.index);
+            true
+        } else {
+            false
+        }
+    }
+    fn victim(&self) -> Option<K> {
+        if self.entries.len() < self.size {
+            return None;
+        }
+        self.queues
+            .iter()
+            .filter_map(|queue| queue.back().map(|index| queue.get(index).clone()))
+            .next()
+    }
+}
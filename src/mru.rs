@@ -5,65 +5,108 @@
 
 use slog;
 
-/// Implementation cache, based on a LRU algorithm.
-pub struct MRUCache {
-    cache: Vec<i32>,
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+/// Implementation cache, based on a MRU algorithm.
+///
+/// Elements are kept in the linked list from the most to the least recently used, the hash map
+/// points to nodes of the list, so every access takes constant time.
+pub struct MRUCache<K> {
+    cache: List<K>,
+    map: HashMap<K, usize>,
     size: usize,
     logger: slog::Logger,
 }
 
-impl MRUCache {
+impl<K: Key> MRUCache<K> {
     /// Create new cache with fix size.
     pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
         let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
         debug!(logger, "Created MRU cache with size: {}", size);
         MRUCache {
-            cache: Vec::with_capacity(size),
+            cache: List::with_capacity(size),
+            map: HashMap::with_capacity(size),
             size: size,
             logger: logger,
         }
     }
+}
 
-    /// Checks the cache to find element. If the cache don't have element, checks the cache size
-    /// and removes old element for pushing new element.
-    ///
-    /// Return `true`, if the cache have element and `false` otherwise.
-    pub fn hit(&mut self, val: &i32) -> bool {
-        if let Some(position) = self.cache.iter().position(|x| x == val) {
+impl<K: Key> CacheAlgorithm<K> for MRUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
             debug!(self.logger, "hit";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
-            let elem = self.cache.remove(position);
-            self.cache.push(elem);
+                   "hit" => format!("{:?}", val));
             true
         } else {
-            if self.cache.len() < self.size {
-                self.cache.push(val.clone());
-            } else {
-                self.cache.pop();
-                self.cache.push(val.clone());
-            }
+            self.insert(val.clone());
             debug!(self.logger, "miss";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.cache.clear();
+        self.map.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+impl<K: Key> Policy<K> for MRUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(&index) = self.map.get(key) {
+            self.cache.move_to_front(index);
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.cache.len() < self.size {
+            None
+        } else {
+            self.cache.pop_front()
+        };
+        if let Some(ref evicted) = evicted {
+            self.map.remove(evicted);
+        }
+        let index = self.cache.push_front(key.clone());
+        self.map.insert(key, index);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(index) = self.map.remove(key) {
+            self.cache.remove(index);
+            true
+        } else {
             false
         }
     }
 
-    /// Run process of checking algorithm.
-    ///
-    /// Return tuple with statistic: `(cache hit, cache miss)`.
-    pub fn run(&mut self, ram: &Vec<Vec<i32>>) -> (i32, i32) {
-        let mut statistic = (0, 0);
-        for batch in ram {
-            for elem in batch {
-                if self.hit(elem) {
-                    statistic.0 += 1;
-                } else {
-                    statistic.1 += 1;
-                }
-            }
+    fn victim(&self) -> Option<K> {
+        if self.cache.len() < self.size {
+            None
+        } else {
+            self.cache.front().map(|index| self.cache.get(index).clone())
         }
-        statistic
     }
 }
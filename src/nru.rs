@@ -0,0 +1,165 @@
+//! Implementation of NRU (Not Recently Used) cache algorithm.
+//!
+//! Every element has a reference bit, which is set when the element is requested. Reference bits of
+//! all elements are periodically cleared (in operating systems it is done by the timer interrupt),
+//! so the set bit means that the element was requested during the current period. Element for
+//! discarding is chosen among elements with cleared bit.
+//!
+//! Operating systems also use the modified bit to prefer clean pages, the simulation has only read
+//! requests, so only the reference bit is used.
+
+use slog;
+
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+
+/// Implementation cache, based on a NRU algorithm.
+///
+/// The period is measured in requests, by default it is equal to size of the cache. The first (by
+/// position in the cache) element with cleared bit is discarded, if all bits are set, the first
+/// element is discarded.
+pub struct NRUCache<K> {
+    slots: Vec<Option<K>>,
+    referenced: Vec<bool>,
+    map: HashMap<K, usize>,
+    free: Vec<usize>,
+    period: usize,
+    time: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> NRUCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created NRU cache with size: {}", size);
+        NRUCache {
+            slots: Vec::with_capacity(size),
+            referenced: Vec::with_capacity(size),
+            map: HashMap::with_capacity(size),
+            free: Vec::new(),
+            period: if size > 0 { size } else { 1 },
+            time: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set count of requests between clearing of reference bits.
+    ///
+    /// Panics, if the period is zero.
+    pub fn with_period(mut self, period: usize) -> Self {
+        assert!(period > 0, "Period must not be zero");
+        self.period = period;
+        debug!(self.logger, format!("With period: {}", self.period));
+        self
+    }
+
+    // Count the request, clear reference bits at the end of the period.
+    fn tick(&mut self) {
+        self.time += 1;
+        if self.time % self.period == 0 {
+            debug!(self.logger, "clear reference bits");
+            for bit in self.referenced.iter_mut() {
+                *bit = false;
+            }
+        }
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for NRUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.slots),
+                   "referenced" => format!("{:?}", self.referenced),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.slots),
+                   "referenced" => format!("{:?}", self.referenced),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.slots.clear();
+        self.referenced.clear();
+        self.map.clear();
+        self.free.clear();
+        self.time = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<K: Key> Policy<K> for NRUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(&slot) = self.map.get(key) {
+            self.tick();
+            self.referenced[slot] = true;
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.tick();
+        let (slot, evicted) = if let Some(slot) = self.free.pop() {
+            (slot, None)
+        } else if self.slots.len() < self.size || self.slots.is_empty() {
+            // The cache with zero size keeps the last element, as `LRUCache` does.
+            self.slots.push(None);
+            self.referenced.push(false);
+            (self.slots.len() - 1, None)
+        } else {
+            let slot = self.referenced.iter().position(|bit| !bit).unwrap_or(0);
+            (slot, self.slots[slot].take())
+        };
+        if let Some(ref evicted) = evicted {
+            self.map.remove(evicted);
+        }
+        self.slots[slot] = Some(key.clone());
+        self.referenced[slot] = true;
+        self.map.insert(key, slot);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(slot) = self.map.remove(key) {
+            self.slots[slot] = None;
+            self.free.push(slot);
+            true
+        } else {
+            false
+        }
+    }
+    fn victim(&self) -> Option<K> {
+        if !self.free.is_empty() || self.slots.len() < self.size || self.slots.is_empty() {
+            return None;
+        }
+        // Reference bits are cleared by the request, which ends the period.
+        let slot = if (self.time + 1) % self.period == 0 {
+            0
+        } else {
+            self.referenced.iter().position(|bit| !bit).unwrap_or(0)
+        };
+        self.slots[slot].clone()
+    }
+}
@@ -0,0 +1,226 @@
+//! Implementation of PLRU (Pseudo-LRU) cache algorithms.
+//!
+//! PLRU algorithms approximate LRU with a few bits per cache line, so they are used in CPU caches,
+//! where exact LRU is too expensive for caches with great associativity.
+//!
+//! Tree-PLRU keeps a binary tree of bits over the cache lines. Every bit points to the half of its
+//! subtree, which was used less recently. On access bits on the path to the line are set to point
+//! away from the line, the line for discarding is found by following the bits from the root.
+//!
+//! Bit-PLRU (also known as MRU-bit) keeps one bit per cache line, which is set on access. When all
+//! bits are set, all bits except the bit of the accessed line are cleared. The first line with
+//! cleared bit is discarded.
+
+use slog;
+
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+
+/// Implementation cache, based on a Tree-PLRU algorithm.
+///
+/// Size of the cache may be not a power of two, then the tree is built for the nearest power of
+/// two and missing lines are never chosen for discarding.
+pub struct TreePLRUCache<K> {
+    lines: Vec<K>,
+    map: HashMap<K, usize>,
+    // Bits of the tree in order of levels, `true` points to the right subtree.
+    tree: Vec<bool>,
+    leaves: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> TreePLRUCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created Tree-PLRU cache with size: {}", size);
+        let leaves = size.next_power_of_two();
+        TreePLRUCache {
+            lines: Vec::with_capacity(size),
+            map: HashMap::with_capacity(size),
+            tree: vec![false; leaves - 1],
+            leaves: leaves,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    // Set bits on the path to the line to point away from the line.
+    fn touch(&mut self, line: usize) {
+        let (mut node, mut low, mut high) = (0, 0, self.leaves);
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+            if line < middle {
+                self.tree[node] = true;
+                node = 2 * node + 1;
+                high = middle;
+            } else {
+                self.tree[node] = false;
+                node = 2 * node + 2;
+                low = middle;
+            }
+        }
+    }
+
+    // Follow bits from the root to the line for discarding.
+    fn victim(&self) -> usize {
+        let (mut node, mut low, mut high) = (0, 0, self.leaves);
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+            if self.tree[node] && middle < self.size {
+                node = 2 * node + 2;
+                low = middle;
+            } else {
+                node = 2 * node + 1;
+                high = middle;
+            }
+        }
+        low
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for TreePLRUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if let Some(&line) = self.map.get(val) {
+            self.touch(line);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.lines),
+                   "tree" => format!("{:?}", self.tree),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            // The cache with zero size keeps the last element, as `LRUCache` does.
+            let line = if self.lines.len() < self.size || self.lines.is_empty() {
+                self.lines.push(val.clone());
+                self.lines.len() - 1
+            } else {
+                let line = self.victim();
+                self.map.remove(&self.lines[line]);
+                self.lines[line] = val.clone();
+                line
+            };
+            self.map.insert(val.clone(), line);
+            self.touch(line);
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.lines),
+                   "tree" => format!("{:?}", self.tree),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.lines.clear();
+        self.map.clear();
+        for bit in self.tree.iter_mut() {
+            *bit = false;
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+/// Implementation cache, based on a Bit-PLRU (MRU-bit) algorithm.
+pub struct BitPLRUCache<K> {
+    lines: Vec<K>,
+    map: HashMap<K, usize>,
+    bits: Vec<bool>,
+    count_set: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> BitPLRUCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created Bit-PLRU cache with size: {}", size);
+        BitPLRUCache {
+            lines: Vec::with_capacity(size),
+            map: HashMap::with_capacity(size),
+            bits: vec![false; size],
+            count_set: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    // Set bit of the line, clear other bits, if all bits are set.
+    fn touch(&mut self, line: usize) {
+        if self.bits.is_empty() {
+            return;
+        }
+        if !self.bits[line] {
+            self.bits[line] = true;
+            self.count_set += 1;
+        }
+        if self.count_set == self.size {
+            for bit in self.bits.iter_mut() {
+                *bit = false;
+            }
+            self.bits[line] = true;
+            self.count_set = 1;
+        }
+    }
+
+    // The first line with cleared bit.
+    fn victim(&self) -> usize {
+        self.bits.iter().position(|bit| !bit).unwrap_or(0)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for BitPLRUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if let Some(&line) = self.map.get(val) {
+            self.touch(line);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.lines),
+                   "bits" => format!("{:?}", self.bits),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            // The cache with zero size keeps the last element, as `LRUCache` does.
+            let line = if self.lines.len() < self.size || self.lines.is_empty() {
+                self.lines.push(val.clone());
+                self.lines.len() - 1
+            } else {
+                let line = self.victim();
+                self.map.remove(&self.lines[line]);
+                self.lines[line] = val.clone();
+                line
+            };
+            self.map.insert(val.clone(), line);
+            self.touch(line);
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.lines),
+                   "bits" => format!("{:?}", self.bits),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.lines.clear();
+        self.map.clear();
+        for bit in self.bits.iter_mut() {
+            *bit = false;
+        }
+        self.count_set = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.lines.len()
+    }
+}
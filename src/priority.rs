@@ -0,0 +1,36 @@
+//! Implementation of priority of elements.
+//!
+//! `f64` is not `Ord`, so it can't be used as a key of the ordered map. `Priority` wraps it for
+//! caches, which order elements by real numbers (`lfuda` and `lrfu` modules).
+
+use std::cmp::Ordering;
+
+/// Priority of the element, which may be used as a key of the ordered map.
+///
+/// Priorities are totally ordered: NaN is equal to NaN and greater than any number, so NaN
+/// priority doesn't break the ordered map.
+#[derive(Debug, Clone, Copy)]
+pub struct Priority(pub f64);
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Priority) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Priority {}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Priority) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Priority) -> Ordering {
+        match self.0.partial_cmp(&other.0) {
+            Some(ordering) => ordering,
+            None => self.0.is_nan().cmp(&other.0.is_nan()),
+        }
+    }
+}
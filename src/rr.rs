@@ -4,71 +4,161 @@
 //! does not require keeping any information about the access history.
 
 use slog;
-use rand::{self, Rng};
-use rand::distributions::{IndependentSample, Range};
+use rand::{self, Rng, SeedableRng, StdRng};
+
+use std::collections::HashMap;
+use std::mem;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
 
 /// Implementation cache, based on a RR algorithm.
-pub struct RRCache {
-    cache: Vec<i32>,
+///
+/// The hash map keeps index of every element in the vector, so the cache is checked in constant
+/// time. Discarded element is replaced by the last element of the vector (swap-remove), new element
+/// is put into the end of the vector.
+///
+/// Random indexes are taken from the generator `R`. The cache, created with the same seed, discards
+/// the same elements on the same requests. The index is taken in advance, so `Policy::victim` tells
+/// the element, which will be discarded.
+pub struct RRCache<K, R = StdRng> {
+    cache: Vec<K>,
+    map: HashMap<K, usize>,
     size: usize,
+    rng: R,
+    next: usize,
+    seed: Option<usize>,
     logger: slog::Logger,
 }
 
-impl RRCache {
-    /// Create new cache with fix size.
+impl<K: Key> RRCache<K> {
+    /// Create new cache with fix size and random seed.
     pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        RRCache::with_seed(size, rand::random(), logger)
+    }
+
+    /// Create new cache with fix size and the seed of random.
+    pub fn with_seed(size: usize, seed: usize, logger: Option<slog::Logger>) -> Self {
+        let mut cache = RRCache::with_rng(size, StdRng::from_seed(&[seed][..]), logger);
+        cache.seed = Some(seed);
+        debug!(cache.logger, "With seed: {}", seed);
+        cache
+    }
+}
+
+impl<K: Key, R: Rng> RRCache<K, R> {
+    /// Create new cache with fix size and the generator of random.
+    pub fn with_rng(size: usize, mut rng: R, logger: Option<slog::Logger>) -> Self {
         let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
         debug!(logger, "Created RR cache with size: {}", size);
+        let next = random_index(&mut rng, size);
         RRCache {
             cache: Vec::with_capacity(size),
+            map: HashMap::with_capacity(size),
             size: size,
+            rng: rng,
+            next: next,
+            seed: None,
             logger: logger,
         }
     }
 
-    /// Checks the cache to find element. If the cache don't have element, checks the cache size
-    /// and removes old element for pushing new element.
-    ///
-    /// Return `true`, if the cache have element and `false` otherwise.
-    pub fn hit<R: Rng>(&mut self, val: &i32, rng: &mut R, range: &Range<usize>) -> bool {
-        if self.cache.contains(val) {
+    /// Seed of random, if the cache was created with the seed.
+    pub fn seed(&self) -> Option<usize> {
+        self.seed
+    }
+}
+
+// Index of the element for discarding, the cache with zero size keeps one element.
+fn random_index<R: Rng>(rng: &mut R, size: usize) -> usize {
+    if size > 0 {
+        rng.gen_range(0, size)
+    } else {
+        0
+    }
+}
+
+impl<K: Key, R: Rng> CacheAlgorithm<K> for RRCache<K, R> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
             debug!(self.logger, "hit";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "hit" => format!("{:?}", val));
             true
         } else {
-            if self.cache.len() < self.size {
-                self.cache.push(val.clone());
-            } else {
-                let random_index = range.ind_sample(rng);
-                debug!(self.logger, "random index is {}", random_index);
-                self.cache.remove(random_index);
-
-                self.cache.push(val.clone());
-            }
+            self.insert(val.clone());
             debug!(self.logger, "miss";
                    "cache" => format!("{:?}", self.cache),
-                   "hit" => format!("{}", val));
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.cache.clear();
+        self.map.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+impl<K: Key, R: Rng> RRCache<K, R> {
+    // Remove element by the index, the last element takes its place.
+    fn swap_remove(&mut self, index: usize) -> K {
+        let elem = self.cache.swap_remove(index);
+        self.map.remove(&elem);
+        if let Some(moved) = self.cache.get(index) {
+            self.map.insert(moved.clone(), index);
+        }
+        elem
+    }
+}
+
+impl<K: Key, R: Rng> Policy<K> for RRCache<K, R> {
+    fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn touch(&mut self, _key: &K) -> Option<K> {
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        // The cache with zero size keeps the last element, as `LRUCache` does.
+        let evicted = if self.cache.len() < self.size || self.cache.is_empty() {
+            None
+        } else {
+            let next = random_index(&mut self.rng, self.size);
+            let random_index = mem::replace(&mut self.next, next);
+            debug!(self.logger, "random index is {}", random_index);
+            Some(self.swap_remove(random_index))
+        };
+        self.map.insert(key.clone(), self.cache.len());
+        self.cache.push(key);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(&index) = self.map.get(key) {
+            self.swap_remove(index);
+            true
+        } else {
             false
         }
     }
 
-    /// Run process of checking algorithm.
-    ///
-    /// Return tuple with statistic: `(cache hit, cache miss)`.
-    pub fn run(&mut self, ram: &Vec<Vec<i32>>) -> (i32, i32) {
-        let mut statistic = (0, 0);
-        let mut rng = rand::thread_rng();
-        let range = Range::new(0, self.size);
-        for batch in ram {
-            for elem in batch {
-                if self.hit(elem, &mut rng, &range) {
-                    statistic.0 += 1;
-                } else {
-                    statistic.1 += 1;
-                }
-            }
+    fn victim(&self) -> Option<K> {
+        if self.cache.len() < self.size || self.cache.is_empty() {
+            None
+        } else {
+            Some(self.cache[self.next].clone())
         }
-        statistic
     }
 }
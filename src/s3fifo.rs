@@ -0,0 +1,244 @@
+//! Implementation of S3-FIFO cache algorithm.
+//!
+//! S3-FIFO uses three FIFO queues: the small queue S, the main queue M and the ghost queue G. New
+//! elements are put into S, most of them are requested only once and are discarded quickly.
+//! Element, which was requested more than once while it was in S (put and hit at least once), is
+//! moved to M. Discarded elements of S are remembered (without data) in G, element from G is put
+//! directly into M. M is managed like CLOCK: element, which was requested, is put again to the head
+//! of M. Every element has two bits frequency counter.
+
+use slog;
+
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+
+// Maximum value of the frequency counter.
+const MAX_FREQ: u8 = 3;
+
+// Queue, which has the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Queue {
+    Small,
+    Main,
+}
+
+/// Implementation cache, based on a S3-FIFO algorithm.
+///
+/// By default S takes 10% of the cache (at least one element), G remembers as many elements as M
+/// may keep. Elements are removed from G lazily: every element of G has generation, which must be
+/// equal to the generation in the hash map of G.
+pub struct S3FifoCache<K> {
+    small: VecDeque<K>,
+    main: VecDeque<K>,
+    entries: HashMap<K, (Queue, u8)>,
+    ghost: VecDeque<(K, u64)>,
+    ghost_map: HashMap<K, u64>,
+    generation: u64,
+    size_small: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> S3FifoCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created S3-FIFO cache with size: {}", size);
+        S3FifoCache {
+            small: VecDeque::with_capacity(size),
+            main: VecDeque::with_capacity(size),
+            entries: HashMap::with_capacity(size),
+            ghost: VecDeque::with_capacity(size),
+            ghost_map: HashMap::with_capacity(size),
+            generation: 0,
+            size_small: size_small(size, 0.1),
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set part of the cache, which is given to the small queue.
+    ///
+    /// Panics, if the ratio is not in `0..=1` range.
+    pub fn with_small(mut self, ratio: f64) -> Self {
+        assert!((0.0..=1.0).contains(&ratio), "Small queue ratio must be in 0..=1 range");
+        self.size_small = size_small(self.size, ratio);
+        debug!(self.logger, format!("With small queue size: {}", self.size_small));
+        self
+    }
+
+    // Remember discarded element of S in G.
+    fn remember(&mut self, key: K) {
+        let size_ghost = self.size - self.size_small;
+        if size_ghost == 0 {
+            return;
+        }
+        self.generation += 1;
+        self.ghost_map.insert(key.clone(), self.generation);
+        self.ghost.push_front((key, self.generation));
+        while self.ghost_map.len() > size_ghost {
+            if let Some((key, generation)) = self.ghost.pop_back() {
+                if self.ghost_map.get(&key) == Some(&generation) {
+                    self.ghost_map.remove(&key);
+                }
+            }
+        }
+        // Drop outdated elements, so the queue doesn't grow with every hit in G.
+        if self.ghost.len() > 2 * size_ghost {
+            let ghost_map = &self.ghost_map;
+            self.ghost = self.ghost
+                .drain(..)
+                .filter(|&(ref key, generation)| ghost_map.get(key) == Some(&generation))
+                .collect();
+        }
+    }
+
+    // Discard element from S, if S is too big, and from M otherwise.
+    fn evict(&mut self) -> Option<K> {
+        loop {
+            if self.small.len() >= self.size_small || self.main.is_empty() {
+                if let Some(key) = self.small.pop_back() {
+                    if self.entries[&key].1 > 0 {
+                        self.entries.insert(key.clone(), (Queue::Main, 0));
+                        self.main.push_front(key);
+                        continue;
+                    }
+                    self.entries.remove(&key);
+                    self.remember(key.clone());
+                    return Some(key);
+                }
+            }
+            match self.main.pop_back() {
+                Some(key) => {
+                    let freq = self.entries[&key].1;
+                    if freq > 0 {
+                        self.entries.insert(key.clone(), (Queue::Main, freq - 1));
+                        self.main.push_front(key);
+                    } else {
+                        self.entries.remove(&key);
+                        return Some(key);
+                    }
+                }
+                None => return None,
+            }
+        }
+    }
+}
+
+// Count of elements of the small queue for the ratio.
+fn size_small(size: usize, ratio: f64) -> usize {
+    cmp::min(cmp::max(1, (size as f64 * ratio) as usize), size)
+}
+
+impl<K: Key> CacheAlgorithm<K> for S3FifoCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "small" => format!("{:?}", self.small),
+                   "main" => format!("{:?}", self.main),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "small" => format!("{:?}", self.small),
+                   "main" => format!("{:?}", self.main),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.small.clear();
+        self.main.clear();
+        self.entries.clear();
+        self.ghost.clear();
+        self.ghost_map.clear();
+        self.generation = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K: Key> Policy<K> for S3FifoCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.1 = cmp::min(entry.1 + 1, MAX_FREQ);
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.entries.len() < self.size {
+            None
+        } else {
+            self.evict()
+        };
+        if self.ghost_map.remove(&key).is_some() {
+            self.entries.insert(key.clone(), (Queue::Main, 0));
+            self.main.push_front(key);
+        } else {
+            self.entries.insert(key.clone(), (Queue::Small, 0));
+            self.small.push_front(key);
+        }
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some((queue, _)) = self.entries.remove(key) {
+            let queue = match queue {
+                Queue::Small => &mut self.small,
+                Queue::Main => &mut self.main,
+            };
+            let position = queue.iter().position(|x| x == key).unwrap();
+            queue.remove(position);
+            true
+        } else {
+            false
+        }
+    }
+    // Follow `evict`, but without moving elements.
+    fn victim(&self) -> Option<K> {
+        if self.entries.len() < self.size {
+            return None;
+        }
+        // Requested elements of S are moved to the head of M with zero counter.
+        let mut small = self.small.iter().rev();
+        let mut small_len = self.small.len();
+        let mut moved = Vec::new();
+        while small_len >= self.size_small || self.main.len() + moved.len() == 0 {
+            match small.next() {
+                Some(key) if self.entries[key].1 > 0 => {
+                    moved.push(key);
+                    small_len -= 1;
+                }
+                Some(key) => return Some(key.clone()),
+                None => break,
+            }
+        }
+        // Every pass over M decrements counters, so the element with the least counter is
+        // discarded, the oldest among equal.
+        self.main
+            .iter()
+            .rev()
+            .map(|key| (self.entries[key].1, key))
+            .chain(moved.into_iter().map(|key| (0, key)))
+            .enumerate()
+            .min_by_key(|&(position, (freq, _))| (freq, position))
+            .map(|(_, (_, key))| key.clone())
+    }
+}
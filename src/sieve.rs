@@ -0,0 +1,150 @@
+//! Implementation of SIEVE cache algorithm.
+//!
+//! SIEVE keeps elements in FIFO queue, every element has visited bit, which is set on hit. The hand
+//! moves from the oldest element to the newest one, clears set bits and discards the first element
+//! with cleared bit. In contrast to CLOCK, retained elements are not moved to the head of the queue,
+//! so new elements, which were not requested again, are discarded quickly, and elements, which were
+//! requested, stay in the old part of the queue.
+
+use slog;
+
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+/// Implementation cache, based on a SIEVE algorithm.
+///
+/// New elements are put to the front of the linked list, the hand moves from the back to the front
+/// and returns to the back after the front.
+pub struct SieveCache<K> {
+    cache: List<K>,
+    map: HashMap<K, (usize, bool)>,
+    hand: Option<usize>,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> SieveCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created SIEVE cache with size: {}", size);
+        SieveCache {
+            cache: List::with_capacity(size),
+            map: HashMap::with_capacity(size),
+            hand: None,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    // Move the hand to the first not visited element, clearing visited bits, and discard it.
+    fn evict(&mut self) -> Option<K> {
+        let mut hand = match self.hand.or(self.cache.back()) {
+            Some(hand) => hand,
+            None => return None,
+        };
+        loop {
+            let visited = &mut self.map.get_mut(self.cache.get(hand)).unwrap().1;
+            if !*visited {
+                break;
+            }
+            *visited = false;
+            hand = self.cache.prev(hand).or(self.cache.back()).unwrap();
+        }
+        self.hand = self.cache.prev(hand);
+        let evicted = self.cache.remove(hand);
+        self.map.remove(&evicted);
+        Some(evicted)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for SieveCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.cache),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.cache),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.cache.clear();
+        self.map.clear();
+        self.hand = None;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+impl<K: Key> Policy<K> for SieveCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(entry) = self.map.get_mut(key) {
+            entry.1 = true;
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.cache.len() < self.size {
+            None
+        } else {
+            self.evict()
+        };
+        let index = self.cache.push_front(key.clone());
+        self.map.insert(key, (index, false));
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some((index, _)) = self.map.remove(key) {
+            if self.hand == Some(index) {
+                self.hand = self.cache.prev(index);
+            }
+            self.cache.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+    fn victim(&self) -> Option<K> {
+        if self.cache.len() < self.size {
+            return None;
+        }
+        // The first not visited element from the hand to the front and then from the back, or the
+        // element under the hand, if all elements are visited.
+        let start = match self.hand.or(self.cache.back()) {
+            Some(hand) => hand,
+            None => return None,
+        };
+        let mut hand = start;
+        for _ in 0..self.cache.len() {
+            let key = self.cache.get(hand);
+            if !self.map[key].1 {
+                return Some(key.clone());
+            }
+            hand = self.cache.prev(hand).or(self.cache.back()).unwrap();
+        }
+        Some(self.cache.get(start).clone())
+    }
+}
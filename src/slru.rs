@@ -11,110 +11,206 @@
 //! accessed before being replaced. The size limit on the protected segment is an SLRU parameter
 //! that varies according to the I/O workload patterns. Whenever data must be discarded from the
 //! cache, lines are obtained from the LRU end of the probationary segment.
+//!
+//! The protected segment may be split into several segments (e.g. 4-segment SLRU has three
+//! protected segments): hit moves the line to the next (higher) segment, the LRU line of the
+//! overflowed segment is moved to the MRU end of the previous (lower) segment.
 
 use slog;
 
-use std::collections::VecDeque;
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
 
-/// Implementation cache, based on a LRU algorithm.
-pub struct SLRUCache {
-    prob_cache: VecDeque<i32>,
-    prot_cache: VecDeque<i32>,
-    size_prob_cache: usize,
-    size_prob_cache_min: usize,
-    size_prot_cache: usize,
+/// Implementation cache, based on a SLRU algorithm.
+///
+/// By default the cache has two segments and the protected part takes half of the cache. The
+/// protected part is divided equally between protected segments. The probationary segment may use
+/// the room, which is not taken by protected segments, so lines are discarded only when the cache is
+/// full and the cache never has more lines than its size. Hits never discard lines.
+pub struct SLRUCache<K> {
+    segments: Vec<List<K>>,
+    sizes: Vec<usize>,
+    map: HashMap<K, (usize, usize)>,
+    protected: f64,
+    size: usize,
     logger: slog::Logger,
 }
 
-impl SLRUCache {
+impl<K: Key> SLRUCache<K> {
     /// Create new cache with fix size.
     pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
         let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
         debug!(logger, "Created SLRU cache with size: {}", size);
-        SLRUCache {
-            prob_cache: VecDeque::with_capacity(size),
-            prot_cache: VecDeque::with_capacity(size / 2),
-            size_prob_cache: size,
-            size_prob_cache_min: size - size / 2,
-            size_prot_cache: size / 2,
+        let mut cache = SLRUCache {
+            segments: Vec::new(),
+            sizes: Vec::new(),
+            map: HashMap::with_capacity(size),
+            protected: 0.5,
+            size: size,
             logger: logger,
-        }
+        };
+        cache.split(2);
+        cache
     }
 
-    /// Checks the cache to find element. If the cache don't have element, checks the cache size
-    /// and removes old element for pushing new element.
+    /// Set part of the cache, which is given to protected segments.
     ///
-    /// Return `true`, if the cache have element and `false` otherwise.
-    pub fn hit(&mut self, val: &i32) -> bool {
-        if let Some(position) = self.prob_cache.iter().position(|x| x == val) {
+    /// Panics, if the part is not in `0..=1` range.
+    pub fn with_protected(mut self, part: f64) -> Self {
+        assert!((0.0..=1.0).contains(&part), "Protected part must be in 0..=1 range");
+        self.protected = part;
+        let count = self.segments.len();
+        self.split(count);
+        debug!(self.logger, format!("With sizes of segments: {:?}", self.sizes));
+        self
+    }
+
+    /// Set count of segments (the probationary segment and protected segments).
+    ///
+    /// Panics, if the count is less than two.
+    pub fn with_segments(mut self, count: usize) -> Self {
+        assert!(count >= 2, "SLRU must have at least two segments");
+        self.split(count);
+        debug!(self.logger, format!("With sizes of segments: {:?}", self.sizes));
+        self
+    }
+
+    /// Count of segments.
+    pub fn segments(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Size of the segment, the probationary segment is the first one.
+    pub fn segment_size(&self, segment: usize) -> usize {
+        self.sizes[segment]
+    }
+
+    /// Count of lines in the segment, the probationary segment is the first one.
+    pub fn segment_len(&self, segment: usize) -> usize {
+        self.segments[segment].len()
+    }
+
+    // Create empty segments and compute their sizes.
+    fn split(&mut self, count: usize) {
+        let size_protected = (self.size as f64 * self.protected) as usize;
+        let count_protected = count - 1;
+        self.sizes = vec![self.size - size_protected];
+        for i in 0..count_protected {
+            let extra = if i < size_protected % count_protected { 1 } else { 0 };
+            self.sizes.push(size_protected / count_protected + extra);
+        }
+        self.segments = (0..count).map(|_| List::with_capacity(0)).collect();
+        self.map.clear();
+    }
+
+    // Put the line to the MRU end of the segment.
+    fn push(&mut self, segment: usize, key: K) {
+        let index = self.segments[segment].push_front(key.clone());
+        self.map.insert(key, (segment, index));
+    }
+
+    // Move LRU lines of overflowed protected segments to lower segments, starting from the segment.
+    fn demote(&mut self, segment: usize) {
+        for segment in (1..segment + 1).rev() {
+            while self.segments[segment].len() > self.sizes[segment] {
+                let key = self.segments[segment].pop_back().unwrap();
+                self.push(segment - 1, key);
+            }
+        }
+    }
+
+    // The lowest segment, which has lines.
+    fn lowest(&self) -> Option<usize> {
+        self.segments.iter().position(|segment| segment.len() > 0)
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for SLRUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
             debug!(self.logger, "hit";
-                   "prob cache" => format!("{:?}", self.prob_cache),
-                   "prot cache" => format!("{:?}", self.prot_cache),
-                   "hit" => format!("{}", val));
-            let elem = self.prob_cache.remove(position).unwrap();
-            self.add_to_protected_cache(elem);
+                   "segments" => format!("{:?}", self.segments),
+                   "hit" => format!("{:?}", val));
             true
         } else {
-            if let Some(position) = self.prot_cache.iter().position(|x| x == val) {
-                debug!(self.logger, "hit";
-                       "prob cache" => format!("{:?}", self.prob_cache),
-                       "prot cache" => format!("{:?}", self.prot_cache),
-                       "hit" => format!("{}", val));
-                let elem = self.prot_cache.remove(position).unwrap();
-                self.prot_cache.push_front(elem);
-                true
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "segments" => format!("{:?}", self.segments),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        for segment in self.segments.iter_mut() {
+            segment.clear();
+        }
+        self.map.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<K: Key> Policy<K> for SLRUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(&(segment, index)) = self.map.get(key) {
+            let key = self.segments[segment].remove(index);
+            let next = if segment + 1 < self.segments.len() {
+                segment + 1
             } else {
-                if self.prob_cache.len() < self.size_prob_cache {
-                    self.prob_cache.push_front(val.clone());
-                } else {
-                    self.prob_cache.pop_back();
-                    self.prob_cache.push_front(val.clone());
-                }
-                debug!(self.logger, "miss";
-                       "prob cache" => format!("{:?}", self.prob_cache),
-                       "prot cache" => format!("{:?}", self.prot_cache),
-                       "hit" => format!("{}", val));
-                false
-            }
+                segment
+            };
+            self.push(next, key);
+            self.demote(next);
         }
+        None
     }
 
-    // Auxiliary function for adding to protected cache in right way.
-    fn add_to_protected_cache(&mut self, val: i32) {
-        if self.prot_cache.len() < self.size_prot_cache {
-            self.prot_cache.push_front(val);
-            if self.size_prob_cache_min != self.size_prob_cache {
-                self.size_prob_cache -= 1;
-            }
+    fn insert(&mut self, key: K) -> Option<K> {
+        let evicted = if self.map.len() < self.size {
+            None
         } else {
-            let to_probationary = self.prot_cache.pop_back().unwrap();
-            self.prot_cache.push_front(val);
-            if self.size_prob_cache_min != self.size_prob_cache {
-                self.size_prob_cache -= 1;
-            }
-            if self.prob_cache.len() < self.size_prot_cache {
-                self.prob_cache.push_front(to_probationary);
-            } else {
-                self.prob_cache.pop_back();
-                self.prob_cache.push_front(to_probationary);
-            }
+            self.lowest().map(|segment| {
+                let evicted = self.segments[segment].pop_back().unwrap();
+                self.map.remove(&evicted);
+                evicted
+            })
+        };
+        self.push(0, key);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some((segment, index)) = self.map.remove(key) {
+            self.segments[segment].remove(index);
+            true
+        } else {
+            false
         }
     }
 
-    /// Run process of checking algorithm.
-    ///
-    /// Return tuple with statistic: `(cache hit, cache miss)`.
-    pub fn run(&mut self, ram: &Vec<Vec<i32>>) -> (i32, i32) {
-        let mut statistic = (0, 0);
-        for batch in ram {
-            for elem in batch {
-                if self.hit(elem) {
-                    statistic.0 += 1;
-                } else {
-                    statistic.1 += 1;
-                }
-            }
+    fn victim(&self) -> Option<K> {
+        if self.map.len() < self.size {
+            None
+        } else {
+            self.lowest().map(|segment| {
+                let list = &self.segments[segment];
+                list.get(list.back().unwrap()).clone()
+            })
         }
-        statistic
     }
 }
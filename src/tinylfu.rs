@@ -0,0 +1,391 @@
+//! Implementation of TinyLFU admission policy and W-TinyLFU cache algorithm.
+//!
+//! TinyLFU decides, whether new element should replace the element, which would be discarded by
+//! the cache algorithm: the element with greater estimated frequency stays in the cache.
+//! Frequencies are approximated by count-min sketch with 4 bits counters. When count of recorded
+//! requests reaches the sample size, all counters are halved (aging), so the sketch follows changes
+//! of the workload. The doorkeeper (Bloom filter) is put in front of the sketch: the first request
+//! of the element is recorded only by the doorkeeper, so elements, which are requested once, don't
+//! take counters. The doorkeeper is cleared on aging.
+//!
+//! W-TinyLFU puts new elements into the small window LRU cache, elements discarded by the window
+//! are candidates for the main SLRU cache, which is guarded by TinyLFU. The window keeps bursts of
+//! new elements, which have not gained frequency yet.
+
+use slog;
+
+use std::cmp;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use lru::LRUCache;
+use slru::SLRUCache;
+
+// Count of rows of the count-min sketch.
+const DEPTH: usize = 4;
+// Maximum value of 4 bits counter.
+const MAX_COUNT: u8 = 15;
+// Count of hash functions of the doorkeeper.
+const DOOR_HASHES: usize = 3;
+
+/// TinyLFU admission policy.
+///
+/// The sketch has four counters per element of the cache (at least 16) in every row, the doorkeeper
+/// has one bit per request of the sample. Elements are hashed by `DefaultHasher` with fixed keys, so
+/// the simulation gives the same results on every run.
+pub struct TinyLFU {
+    counters: Vec<u8>,
+    doorkeeper: Vec<u64>,
+    width: usize,
+    door_bits: usize,
+    additions: usize,
+    sample: usize,
+}
+
+impl TinyLFU {
+    /// Create new admission policy for the cache with fix size.
+    ///
+    /// The sample size is ten times size of the cache.
+    pub fn new(size: usize) -> Self {
+        TinyLFU::with_sample(size, cmp::max(1, 10 * size))
+    }
+
+    /// Create new admission policy for the cache with fix size, which halves counters after
+    /// `sample` requests.
+    ///
+    /// Panics, if the sample size is zero.
+    pub fn with_sample(size: usize, sample: usize) -> Self {
+        assert!(sample > 0, "Sample size must not be zero");
+        let width = cmp::max(16, 4 * size).next_power_of_two();
+        let door_bits = cmp::max(64, sample).next_power_of_two();
+        TinyLFU {
+            counters: vec![0; DEPTH * width],
+            doorkeeper: vec![0; door_bits / 64],
+            width: width,
+            door_bits: door_bits,
+            additions: 0,
+            sample: sample,
+        }
+    }
+
+    /// Record request of the element.
+    pub fn increment<K: Hash>(&mut self, key: &K) {
+        let (h1, h2) = hashes(key);
+        if self.door_contains(h1, h2) {
+            for row in 0..DEPTH {
+                let index = self.counter(row, h1, h2);
+                if self.counters[index] < MAX_COUNT {
+                    self.counters[index] += 1;
+                }
+            }
+        } else {
+            for i in 0..DOOR_HASHES {
+                let bit = self.door_bit(i, h1, h2);
+                self.doorkeeper[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        self.additions += 1;
+        if self.additions >= self.sample {
+            self.age();
+        }
+    }
+
+    /// Estimated count of requests of the element.
+    pub fn frequency<K: Hash>(&self, key: &K) -> u8 {
+        let (h1, h2) = hashes(key);
+        let count = (0..DEPTH)
+            .map(|row| self.counters[self.counter(row, h1, h2)])
+            .min()
+            .unwrap();
+        if self.door_contains(h1, h2) {
+            count + 1
+        } else {
+            count
+        }
+    }
+
+    /// Return `true`, if the candidate should replace the victim in the cache.
+    ///
+    /// The candidate is admitted only if it is requested more often than the victim.
+    pub fn admit<K: Hash>(&self, candidate: &K, victim: &K) -> bool {
+        self.frequency(candidate) > self.frequency(victim)
+    }
+
+    /// Forget all requests.
+    pub fn clear(&mut self) {
+        for counter in self.counters.iter_mut() {
+            *counter = 0;
+        }
+        for word in self.doorkeeper.iter_mut() {
+            *word = 0;
+        }
+        self.additions = 0;
+    }
+
+    // Halve all counters and clear the doorkeeper.
+    fn age(&mut self) {
+        for counter in self.counters.iter_mut() {
+            *counter /= 2;
+        }
+        for word in self.doorkeeper.iter_mut() {
+            *word = 0;
+        }
+        self.additions /= 2;
+    }
+
+    // Index of the counter of the row.
+    fn counter(&self, row: usize, h1: u64, h2: u64) -> usize {
+        let hash = h1.wrapping_add((row as u64).wrapping_mul(h2));
+        row * self.width + (hash as usize & (self.width - 1))
+    }
+
+    // Index of the bit of the doorkeeper for the hash function.
+    fn door_bit(&self, i: usize, h1: u64, h2: u64) -> usize {
+        let hash = h2.wrapping_add((i as u64).wrapping_mul(h1));
+        hash as usize & (self.door_bits - 1)
+    }
+
+    fn door_contains(&self, h1: u64, h2: u64) -> bool {
+        (0..DOOR_HASHES).all(|i| {
+            let bit = self.door_bit(i, h1, h2);
+            self.doorkeeper[bit / 64] & (1 << (bit % 64)) != 0
+        })
+    }
+}
+
+// Two independent hashes of the element for double hashing.
+fn hashes<K: Hash>(key: &K) -> (u64, u64) {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    let hash = hasher.finish();
+    (hash, hash.rotate_right(32) | 1)
+}
+
+/// Cache algorithm, guarded by TinyLFU admission policy.
+///
+/// New element is put into the cache only if it is requested more often than the element, which
+/// would be discarded (see `Policy::victim`). The rejected element is returned by `insert` as the
+/// evicted one, the state of the algorithm is not changed. If the algorithm can't tell the victim,
+/// every element is put into the cache.
+pub struct TinyLFUCache<K> {
+    policy: Box<dyn Policy<K>>,
+    filter: TinyLFU,
+    logger: slog::Logger,
+}
+
+impl<K: Key + 'static> TinyLFUCache<K> {
+    /// Create new cache with fix size, based on a LRU algorithm.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        TinyLFUCache::with_policy(Box::new(LRUCache::new(size, logger.clone())), logger)
+    }
+}
+
+impl<K: Key> TinyLFUCache<K> {
+    /// Create new cache, based on the cache algorithm, with size of the algorithm.
+    pub fn with_policy(policy: Box<dyn Policy<K>>, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        let size = policy.capacity();
+        debug!(logger, "Created TinyLFU cache with size: {}", size);
+        TinyLFUCache {
+            policy: policy,
+            filter: TinyLFU::new(size),
+            logger: logger,
+        }
+    }
+
+    /// Set count of requests, after which counters of TinyLFU are halved.
+    ///
+    /// Panics, if the sample size is zero.
+    pub fn with_sample(mut self, sample: usize) -> Self {
+        self.filter = TinyLFU::with_sample(self.policy.capacity(), sample);
+        debug!(self.logger, format!("With sample size: {}", sample));
+        self
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for TinyLFUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "frequency" => self.filter.frequency(val),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "frequency" => self.filter.frequency(val),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.policy.reset();
+        self.filter.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.policy.capacity()
+    }
+
+    fn len(&self) -> usize {
+        self.policy.len()
+    }
+}
+
+impl<K: Key> Policy<K> for TinyLFUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.policy.contains(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        self.filter.increment(key);
+        self.policy.touch(key)
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.filter.increment(&key);
+        match self.policy.victim() {
+            Some(ref victim) if !self.filter.admit(&key, victim) => {
+                debug!(self.logger, "reject"; "key" => format!("{:?}", key));
+                Some(key)
+            }
+            _ => self.policy.insert(key),
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        self.policy.remove(key)
+    }
+}
+
+/// Implementation cache, based on a W-TinyLFU algorithm.
+///
+/// By default the window takes 1% of the cache (at least one element), the rest is given to the
+/// main SLRU cache, 80% of which is protected.
+pub struct WTinyLFUCache<K> {
+    window: LRUCache<K>,
+    main: SLRUCache<K>,
+    filter: TinyLFU,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> WTinyLFUCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created W-TinyLFU cache with size: {}", size);
+        let size_window = size_window(size, 0.01);
+        WTinyLFUCache {
+            window: LRUCache::new(size_window, Some(logger.clone())),
+            main: SLRUCache::new(size - size_window, Some(logger.clone())).with_protected(0.8),
+            filter: TinyLFU::new(size),
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set part of the cache, which is given to the window.
+    ///
+    /// Panics, if the part is not in `0..=1` range.
+    pub fn with_window(mut self, part: f64) -> Self {
+        assert!((0.0..=1.0).contains(&part), "Window part must be in 0..=1 range");
+        let size_window = size_window(self.size, part);
+        self.window = LRUCache::new(size_window, Some(self.logger.clone()));
+        self.main = SLRUCache::new(self.size - size_window, Some(self.logger.clone()))
+            .with_protected(0.8);
+        debug!(self.logger, format!("With window size: {}", size_window));
+        self
+    }
+
+    /// Set count of requests, after which counters of TinyLFU are halved.
+    ///
+    /// Panics, if the sample size is zero.
+    pub fn with_sample(mut self, sample: usize) -> Self {
+        self.filter = TinyLFU::with_sample(self.size, sample);
+        debug!(self.logger, format!("With sample size: {}", sample));
+        self
+    }
+}
+
+// Count of elements of the window for the part.
+fn size_window(size: usize, part: f64) -> usize {
+    cmp::min(cmp::max(1, (size as f64 * part) as usize), size)
+}
+
+impl<K: Key> CacheAlgorithm<K> for WTinyLFUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "window" => self.window.len(),
+                   "main" => self.main.len(),
+                   "frequency" => self.filter.frequency(val),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "window" => self.window.len(),
+                   "main" => self.main.len(),
+                   "frequency" => self.filter.frequency(val),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.main.reset();
+        self.filter.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.window.len() + self.main.len()
+    }
+}
+
+impl<K: Key> Policy<K> for WTinyLFUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.window.contains(key) || self.main.contains(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        self.filter.increment(key);
+        if self.window.contains(key) {
+            self.window.touch(key)
+        } else {
+            self.main.touch(key)
+        }
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.filter.increment(&key);
+        let candidate = match self.window.insert(key) {
+            Some(candidate) => candidate,
+            None => return None,
+        };
+        if self.main.capacity() == 0 {
+            return Some(candidate);
+        }
+        match self.main.victim() {
+            Some(ref victim) if !self.filter.admit(&candidate, victim) => {
+                debug!(self.logger, "reject"; "key" => format!("{:?}", candidate));
+                Some(candidate)
+            }
+            _ => self.main.insert(candidate),
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        self.window.remove(key) || self.main.remove(key)
+    }
+}
@@ -0,0 +1,214 @@
+//! Implementation of 2Q cache algorithm.
+//!
+//! 2Q keeps new elements in the FIFO queue A1in. Elements, discarded from A1in, are remembered
+//! (without data) in the FIFO queue A1out. Element, which is requested again while it is in A1out,
+//! is considered as hot and is put into the LRU queue Am. Hits in A1in don't change anything, so
+//! elements, which are requested several times during short period, don't pollute Am.
+//!
+//! Size of A1in (Kin) and size of A1out (Kout) are parameters of the algorithm, they are set as
+//! fractions of size of the cache. Authors of the algorithm recommend 25% for Kin and 50% for
+//! Kout.
+
+use slog;
+
+use std::collections::HashMap;
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+use list::List;
+
+// Queue, which has the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Queue {
+    In,
+    Out,
+    Main,
+}
+
+/// Implementation cache, based on a 2Q algorithm.
+///
+/// Elements of A1out are not counted as elements of the cache.
+pub struct TwoQCache<K> {
+    a1in: List<K>,
+    a1out: List<K>,
+    am: List<K>,
+    map: HashMap<K, (Queue, usize)>,
+    kin: usize,
+    kout: usize,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> TwoQCache<K> {
+    /// Create new cache with fix size, Kin is 25% and Kout is 50% of size of the cache.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created 2Q cache with size: {}", size);
+        TwoQCache {
+            a1in: List::with_capacity(size),
+            a1out: List::with_capacity(size),
+            am: List::with_capacity(size),
+            map: HashMap::with_capacity(size * 2),
+            kin: fraction(size, 0.25),
+            kout: fraction(size, 0.5),
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set size of A1in as fraction of size of the cache.
+    ///
+    /// Panics, if the fraction is not in `0..=1` range.
+    pub fn with_kin(mut self, kin: f64) -> Self {
+        assert!((0.0..=1.0).contains(&kin), "Kin must be in 0..=1 range");
+        self.kin = fraction(self.size, kin);
+        debug!(self.logger, format!("With Kin: {}", self.kin));
+        self
+    }
+
+    /// Set size of A1out as fraction of size of the cache.
+    ///
+    /// Panics, if the fraction is negative.
+    pub fn with_kout(mut self, kout: f64) -> Self {
+        assert!(kout >= 0.0, "Kout must not be negative");
+        self.kout = fraction(self.size, kout);
+        debug!(self.logger, format!("With Kout: {}", self.kout));
+        self
+    }
+
+    // Make room for new element, return discarded element.
+    //
+    // Element of A1in is remembered in A1out, element of Am is forgotten.
+    fn reclaim(&mut self) -> Option<K> {
+        if self.a1in.len() + self.am.len() < self.size {
+            return None;
+        }
+        if self.a1in.len() > self.kin || self.am.len() == 0 {
+            let evicted = self.a1in.pop_back();
+            if let Some(ref evicted) = evicted {
+                if self.kout > 0 {
+                    let index = self.a1out.push_front(evicted.clone());
+                    self.map.insert(evicted.clone(), (Queue::Out, index));
+                    if self.a1out.len() > self.kout {
+                        if let Some(forgotten) = self.a1out.pop_back() {
+                            self.map.remove(&forgotten);
+                        }
+                    }
+                } else {
+                    self.map.remove(evicted);
+                }
+            }
+            evicted
+        } else {
+            let evicted = self.am.pop_back();
+            if let Some(ref evicted) = evicted {
+                self.map.remove(evicted);
+            }
+            evicted
+        }
+    }
+}
+
+// Count of elements, which is the fraction of size of the cache.
+fn fraction(size: usize, fraction: f64) -> usize {
+    (size as f64 * fraction) as usize
+}
+
+impl<K: Key> CacheAlgorithm<K> for TwoQCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "a1in" => format!("{:?}", self.a1in),
+                   "a1out" => format!("{:?}", self.a1out),
+                   "am" => format!("{:?}", self.am),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "a1in" => format!("{:?}", self.a1in),
+                   "a1out" => format!("{:?}", self.a1out),
+                   "am" => format!("{:?}", self.am),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.a1in.clear();
+        self.a1out.clear();
+        self.am.clear();
+        self.map.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.a1in.len() + self.am.len()
+    }
+}
+
+impl<K: Key> Policy<K> for TwoQCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        match self.map.get(key) {
+            Some(&(Queue::In, _)) |
+            Some(&(Queue::Main, _)) => true,
+            _ => false,
+        }
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if let Some(&(Queue::Main, index)) = self.map.get(key) {
+            self.am.move_to_front(index);
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        let remembered = match self.map.get(&key) {
+            Some(&(Queue::Out, index)) => Some(index),
+            _ => None,
+        };
+        if let Some(index) = remembered {
+            self.a1out.remove(index);
+            self.map.remove(&key);
+        }
+        let evicted = self.reclaim();
+        let place = if remembered.is_some() {
+            (Queue::Main, self.am.push_front(key.clone()))
+        } else {
+            (Queue::In, self.a1in.push_front(key.clone()))
+        };
+        self.map.insert(key, place);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        match self.map.get(key).cloned() {
+            Some((Queue::In, index)) => {
+                self.a1in.remove(index);
+            }
+            Some((Queue::Main, index)) => {
+                self.am.remove(index);
+            }
+            _ => return false,
+        }
+        self.map.remove(key);
+        true
+    }
+
+    fn victim(&self) -> Option<K> {
+        if self.a1in.len() + self.am.len() < self.size {
+            return None;
+        }
+        let list = if self.a1in.len() > self.kin || self.am.len() == 0 {
+            &self.a1in
+        } else {
+            &self.am
+        };
+        list.back().map(|index| list.get(index).clone())
+    }
+}
@@ -0,0 +1,187 @@
+//! Implementation of WLFU (Window LFU) cache algorithm.
+//!
+//! In contrast to LFU, which counts requests from the beginning, WLFU counts requests only in the
+//! window of W last requests (including requests of elements, which are not in the cache), so
+//! elements, which were hot long ago, are not kept in the cache forever. The element with the least
+//! count of requests in the window is discarded, among them the least recently used one.
+
+use slog;
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use {CacheAlgorithm, Key};
+use cache::Policy;
+
+/// Implementation cache, based on a WLFU algorithm.
+///
+/// By default the window has ten sizes of the cache requests. Elements of the cache are ordered by
+/// count of requests and time of the last request.
+pub struct WLFUCache<K> {
+    window: VecDeque<K>,
+    counts: HashMap<K, usize>,
+    resident: HashMap<K, (usize, u64)>,
+    order: BTreeMap<(usize, u64), K>,
+    window_size: usize,
+    time: u64,
+    size: usize,
+    logger: slog::Logger,
+}
+
+impl<K: Key> WLFUCache<K> {
+    /// Create new cache with fix size.
+    pub fn new(size: usize, logger: Option<slog::Logger>) -> Self {
+        let logger = logger.unwrap_or(slog::Logger::root(slog::Discard, o!()));
+        debug!(logger, "Created WLFU cache with size: {}", size);
+        let window_size = if size > 0 { 10 * size } else { 1 };
+        WLFUCache {
+            window: VecDeque::with_capacity(window_size + 1),
+            counts: HashMap::new(),
+            resident: HashMap::with_capacity(size),
+            order: BTreeMap::new(),
+            window_size: window_size,
+            time: 0,
+            size: size,
+            logger: logger,
+        }
+    }
+
+    /// Set count of requests in the window.
+    ///
+    /// Panics, if the window is empty.
+    pub fn with_window(mut self, window_size: usize) -> Self {
+        assert!(window_size > 0, "Window must not be empty");
+        self.window_size = window_size;
+        debug!(self.logger, format!("With window: {}", self.window_size));
+        self
+    }
+
+    /// Count of requests of the element in the window.
+    pub fn frequency(&self, key: &K) -> usize {
+        self.counts.get(key).cloned().unwrap_or(0)
+    }
+
+    // Record the request in the window, the oldest request leaves the window.
+    fn tick(&mut self, key: &K) {
+        self.time += 1;
+        self.window.push_back(key.clone());
+        *self.counts.entry(key.clone()).or_insert(0) += 1;
+        if self.window.len() > self.window_size {
+            let old = self.window.pop_front().unwrap();
+            let count = self.counts[&old] - 1;
+            if count == 0 {
+                self.counts.remove(&old);
+            } else {
+                self.counts.insert(old.clone(), count);
+            }
+            self.reorder(&old, None);
+        }
+    }
+
+    // Update position of the element in the order, if the element is in the cache.
+    fn reorder(&mut self, key: &K, last: Option<u64>) {
+        if let Some(entry) = self.resident.get_mut(key) {
+            self.order.remove(entry);
+            *entry = (self.counts.get(key).cloned().unwrap_or(0), last.unwrap_or(entry.1));
+            self.order.insert(*entry, key.clone());
+        }
+    }
+}
+
+impl<K: Key> CacheAlgorithm<K> for WLFUCache<K> {
+    fn access(&mut self, val: &K) -> bool {
+        if self.contains(val) {
+            self.touch(val);
+            debug!(self.logger, "hit";
+                   "cache" => format!("{:?}", self.order),
+                   "hit" => format!("{:?}", val));
+            true
+        } else {
+            self.insert(val.clone());
+            debug!(self.logger, "miss";
+                   "cache" => format!("{:?}", self.order),
+                   "hit" => format!("{:?}", val));
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.counts.clear();
+        self.resident.clear();
+        self.order.clear();
+        self.time = 0;
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn len(&self) -> usize {
+        self.resident.len()
+    }
+}
+
+impl<K: Key> Policy<K> for WLFUCache<K> {
+    fn contains(&self, key: &K) -> bool {
+        self.resident.contains_key(key)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<K> {
+        if self.contains(key) {
+            self.tick(key);
+            let time = self.time;
+            self.reorder(key, Some(time));
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K) -> Option<K> {
+        self.tick(&key);
+        let evicted = if self.resident.len() < self.size {
+            None
+        } else {
+            let first = self.order.keys().next().cloned();
+            first.map(|first| {
+                let evicted = self.order.remove(&first).unwrap();
+                self.resident.remove(&evicted);
+                evicted
+            })
+        };
+        let entry = (self.frequency(&key), self.time);
+        self.order.insert(entry, key.clone());
+        self.resident.insert(key, entry);
+        evicted
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        if let Some(entry) = self.resident.remove(key) {
+            self.order.remove(&entry);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn victim(&self) -> Option<K> {
+        if self.resident.len() < self.size {
+            return None;
+        }
+        // The request of new element pushes the oldest request out of the window, so the count of
+        // its element is decremented before discarding.
+        let leaving = if self.window.len() + 1 > self.window_size {
+            self.window.front().and_then(|old| {
+                self.resident.get(old).map(|&(count, last)| ((count - 1, last), old.clone()))
+            })
+        } else {
+            None
+        };
+        let first = self.order
+            .iter()
+            .find(|&(_, key)| leaving.as_ref().map_or(true, |&(_, ref old)| key != old))
+            .map(|(&entry, key)| (entry, key.clone()));
+        match (first, leaving) {
+            (Some(first), Some(leaving)) => Some(if leaving.0 < first.0 { leaving.1 } else { first.1 }),
+            (first, leaving) => first.or(leaving).map(|(_, key)| key),
+        }
+    }
+}
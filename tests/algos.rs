@@ -0,0 +1,100 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::Algos;
+use cache_algos::cache;
+
+use std::collections::HashSet;
+
+const ALGOS: [Algos; 29] = [Algos::Belady, Algos::FIFO, Algos::LRU, Algos::LFU, Algos::RR,
+                            Algos::MRU, Algos::PLRU, Algos::BitPLRU, Algos::SLRU, Algos::TwoQ,
+                            Algos::ARC, Algos::CAR, Algos::CART, Algos::MQ, Algos::LIRS,
+                            Algos::CLOCK, Algos::GCLOCK, Algos::NRU, Algos::ClockPro,
+                            Algos::SIEVE, Algos::S3FIFO, Algos::TinyLFU, Algos::WTinyLFU,
+                            Algos::LRU2, Algos::LFUDA, Algos::GDSF, Algos::WLFU, Algos::LRFU,
+                            Algos::LeCaR];
+
+#[test]
+fn parse_display_round_trip() {
+    for algo in ALGOS.iter() {
+        assert_eq!(algo.to_string().parse::<Algos>(), Ok(*algo));
+    }
+}
+
+#[test]
+fn random_algorithms() {
+    assert!(Algos::RR.uses_random());
+    assert!(Algos::LeCaR.uses_random());
+    assert!(!Algos::LRU.uses_random());
+    assert!(!Algos::Belady.uses_random());
+}
+
+#[test]
+fn parse_unknown() {
+    assert!("LRU3".parse::<Algos>().is_err());
+}
+
+#[test]
+fn init_by_name() {
+    let cache = cache_algos::init::<i32>("LRU".parse().unwrap(), 42, None);
+    assert_eq!(cache.capacity(), 42);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn size_is_not_exceeded() {
+    let ram = common::random_ram(10);
+    for algo in ALGOS.iter() {
+        for size in &common::SIZES {
+            let mut cache = cache_algos::init_with_seed(*algo, *size, 10, None);
+            common::check_size(&mut *cache, &ram, |_| ());
+        }
+    }
+}
+
+#[test]
+fn accessed_element_is_in_policy() {
+    let ram = common::random_ram(10);
+    for algo in ALGOS.iter() {
+        // TinyLFU may reject the accessed element, so only its size is checked.
+        if *algo == Algos::TinyLFU || *algo == Algos::WTinyLFU {
+            continue;
+        }
+        for size in &common::SIZES {
+            if let Ok(mut policy) = cache::policy_with_seed(*algo, *size, 10, None) {
+                common::check_policy(&mut *policy, &ram, |_| ());
+            }
+        }
+    }
+}
+
+#[test]
+fn victim_is_evicted() {
+    let ram = common::random_ram(10);
+    for algo in ALGOS.iter() {
+        if *algo == Algos::TinyLFU || *algo == Algos::WTinyLFU {
+            continue;
+        }
+        for size in &common::SIZES {
+            let mut policy = match cache::policy_with_seed(*algo, *size, 10, None) {
+                Ok(policy) => policy,
+                Err(_) => continue,
+            };
+            let mut requested = HashSet::new();
+            for elem in ram.iter().flat_map(|batch| batch.iter()) {
+                if policy.contains(elem) {
+                    policy.touch(elem);
+                    continue;
+                }
+                let victim = policy.victim();
+                let evicted = policy.insert(*elem);
+                // ARC and LeCaR learn from the history before discarding, so the victim is told
+                // only for new elements.
+                if requested.insert(*elem) || (*algo != Algos::ARC && *algo != Algos::LeCaR) {
+                    assert_eq!(victim, evicted, "{} with size {}", algo, size);
+                }
+            }
+        }
+    }
+}
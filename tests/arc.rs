@@ -0,0 +1,62 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::arc::ARCCache;
+use cache_algos::cache::Policy;
+use cache_algos::lru::LRUCache;
+
+#[test]
+fn frequent_elements_survive_scan() {
+    // `1` and `2` are put into T2, `p` stays zero, so the scan of `3..8` discards only elements of
+    // T1.
+    let ram = vec![vec![1, 2, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2]];
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (2, 10));
+    let mut arc_cache = ARCCache::new(4, None);
+    assert_eq!(arc_cache.run(&ram), (4, 8));
+    assert_eq!(arc_cache.p(), 0);
+}
+
+#[test]
+fn p_is_adapted() {
+    let mut arc_cache = ARCCache::new(4, None);
+    // `2` is discarded to B1 by `5`, then it is requested again.
+    let ram = vec![vec![1, 2, 1, 3, 4, 5, 2]];
+    assert_eq!(arc_cache.run(&ram), (1, 6));
+    assert_eq!(arc_cache.p(), 1);
+    assert!(arc_cache.contains(&2));
+    assert!(!arc_cache.contains(&3));
+
+    // `1` is discarded from T2 to B2 and requested again.
+    assert_eq!(arc_cache.insert(6), Some(4));
+    arc_cache.touch(&6);
+    assert_eq!(arc_cache.insert(7), Some(1));
+    assert_eq!(arc_cache.insert(1), Some(5));
+    assert_eq!(arc_cache.p(), 0);
+
+    arc_cache.reset();
+    assert_eq!(arc_cache.p(), 0);
+    assert!(arc_cache.is_empty());
+}
+
+#[test]
+fn t1_and_b1_are_bounded() {
+    // T1 is full, so its LRU element is discarded without remembering in B1 (T1 and B1 together
+    // never have more than size elements), misses of the loop never hit B1 and `p` is not changed.
+    let ram: Vec<Vec<i32>> = (0..10).map(|_| (0..5).collect()).collect();
+
+    let mut arc_cache = ARCCache::new(4, None);
+    assert_eq!(arc_cache.run(&ram), (0, 50));
+    assert_eq!(arc_cache.p(), 0);
+}
+
+#[test]
+fn p_is_bounded() {
+    let ram = common::random_ram(12);
+    for size in &common::SIZES {
+        let mut arc_cache = ARCCache::new(*size, None);
+        common::check_policy(&mut arc_cache, &ram, |cache| assert!(cache.p() <= *size));
+    }
+}
@@ -0,0 +1,130 @@
+extern crate cache_algos;
+
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::assoc::{Indexing, SetAssociativeCache};
+use cache_algos::lru::LRUCache;
+use cache_algos::memory::RamBuilder;
+
+#[test]
+fn geometry() {
+    let cache = SetAssociativeCache::<u64>::new(Algos::LRU, 1024, 16, 4, None);
+    assert_eq!(cache.sets(), 16);
+    assert_eq!(cache.ways(), 4);
+    assert_eq!(cache.line_size(), 16);
+    assert_eq!(cache.size(), 1024);
+    assert_eq!(cache.capacity(), 64);
+
+    // Offset in the line is 4 bits, index of the set is the next 4 bits.
+    assert_eq!(cache.locate(&0x1234), (0x123, 3));
+    assert_eq!(cache.locate(&0x1204), (0x120, 0));
+}
+
+#[test]
+fn one_set_same_as_fully_associative() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 30)
+        .with_seed(25)
+        .build();
+
+    for algo in &[Algos::Belady, Algos::FIFO, Algos::LRU, Algos::PLRU, Algos::BitPLRU] {
+        let expected = cache_algos::init_with_seed(*algo, 8, 7, None).run(&ram);
+        let mut cache = SetAssociativeCache::with_seed(*algo, 8, 1, 8, 7, None);
+        assert_eq!(cache.run(&ram), expected);
+        assert_eq!(cache.len(), 8);
+    }
+}
+
+#[test]
+fn conflict_misses() {
+    // `0` and `4` fall into the same set of direct-mapped cache.
+    let ram = vec![vec![0, 4, 0, 4, 1, 1]];
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (3, 3));
+    let mut cache = SetAssociativeCache::new(Algos::LRU, 4, 1, 1, None);
+    assert_eq!(cache.run(&ram), (1, 5));
+    assert_eq!(cache.set_len(0), 1);
+    assert_eq!(cache.set_len(1), 1);
+}
+
+#[test]
+fn line_size() {
+    // Eight bytes of two lines.
+    let ram = vec![(0..8).collect()];
+
+    let mut cache = SetAssociativeCache::new(Algos::FIFO, 16, 4, 2, None);
+    assert_eq!(cache.run(&ram), (6, 2));
+    assert_eq!(cache.len(), 2);
+    cache.reset();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn xor_indexing_spreads_stride() {
+    // Lines `0, 4, 8, 12` fall into the first set with modulo indexing.
+    let ram: Vec<Vec<u64>> = (0..10).map(|_| vec![0, 4, 8, 12]).collect();
+
+    let mut cache = SetAssociativeCache::new(Algos::LRU, 4, 1, 1, None);
+    assert_eq!(cache.run(&ram), (0, 40));
+    let mut cache = SetAssociativeCache::new(Algos::LRU, 4, 1, 1, None)
+        .with_indexing(Indexing::XOR);
+    assert_eq!(cache.run(&ram), (36, 4));
+}
+
+#[test]
+fn run_same_as_access() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 200)
+        .with_seed(25)
+        .build();
+
+    for algo in &[Algos::LRU, Algos::FIFO, Algos::RR, Algos::PLRU] {
+        let expected = SetAssociativeCache::with_seed(*algo, 64, 4, 4, 7, None).run(&ram);
+        let mut cache = SetAssociativeCache::with_seed(*algo, 64, 4, 4, 7, None);
+        let mut statistic = (0, 0);
+        for elem in ram.iter().flat_map(|batch| batch.iter()) {
+            if cache.access(elem) {
+                statistic.0 += 1;
+            } else {
+                statistic.1 += 1;
+            }
+        }
+        assert_eq!(statistic, expected);
+    }
+}
+
+#[test]
+fn same_seed_same_result() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 200)
+        .with_seed(3)
+        .build();
+
+    for algo in &[Algos::RR, Algos::LeCaR] {
+        let expected = SetAssociativeCache::with_seed(*algo, 64, 4, 4, 7, None).run(&ram);
+        for _ in 0..5 {
+            let mut cache = SetAssociativeCache::with_seed(*algo, 64, 4, 4, 7, None);
+            assert_eq!(cache.run(&ram), expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn size_not_multiple_of_sets() {
+    SetAssociativeCache::<i32>::new(Algos::LRU, 100, 16, 4, None);
+}
+
+#[test]
+#[should_panic]
+fn xor_needs_power_of_two_sets() {
+    SetAssociativeCache::<i32>::new(Algos::LRU, 12, 1, 4, None).with_indexing(Indexing::XOR);
+}
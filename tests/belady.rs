@@ -0,0 +1,68 @@
+extern crate cache_algos;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::belady::BeladyCache;
+use cache_algos::memory::RamBuilder;
+
+use std::collections::VecDeque;
+
+/// Previous implementation of Bélády's algorithm, which scans the future on every miss.
+fn naive_belady(ram: &Vec<Vec<i32>>, size: usize) -> (i32, i32) {
+    let mut statistic = (0, 0);
+    let mut cache: Vec<i32> = Vec::new();
+    let mut future: VecDeque<i32> = ram.iter().flat_map(|batch| batch.iter().cloned()).collect();
+    while let Some(elem) = future.pop_front() {
+        if cache.contains(&elem) {
+            statistic.0 += 1;
+        } else {
+            statistic.1 += 1;
+            if cache.len() >= size {
+                let usage: Vec<Option<usize>> = cache.iter()
+                    .map(|x| future.iter().position(|y| y == x))
+                    .collect();
+                let index = match usage.iter().position(|x| x.is_none()) {
+                    Some(index) => index,
+                    None => usage.iter().position(|x| x == usage.iter().max().unwrap()).unwrap(),
+                };
+                cache.remove(index);
+            }
+            cache.push(elem);
+        }
+    }
+    statistic
+}
+
+#[test]
+fn check_simple_hit() {
+    let ram = vec![vec![1, 2, 3, 1, 2, 4, 1, 2, 3]];
+
+    let mut belady_cache = BeladyCache::new(3, None);
+    assert_eq!(belady_cache.run(&ram), (4, 5));
+}
+
+#[test]
+fn same_as_naive_on_random_ram() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 200)
+        .build();
+
+    for size in &[2, 10, 64, 150, 250] {
+        let mut belady_cache = BeladyCache::new(*size, None);
+        assert_eq!(belady_cache.run(&ram), naive_belady(&ram, *size));
+    }
+}
+
+#[test]
+fn run_twice() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(10)
+        .with_size_batch(10)
+        .build();
+
+    let mut belady_cache = BeladyCache::new(5, None);
+    let first = belady_cache.run(&ram);
+    assert_eq!(belady_cache.run(&ram), first);
+}
@@ -0,0 +1,83 @@
+extern crate cache_algos;
+
+use cache_algos::Algos;
+use cache_algos::cache::Cache;
+use cache_algos::memory::RamBuilder;
+
+#[test]
+fn lru_put_get() {
+    let mut cache = Cache::with_algo(Algos::LRU, 2, None).unwrap();
+    assert_eq!(cache.put(1, "one"), None);
+    assert_eq!(cache.put(2, "two"), None);
+    assert_eq!(cache.get(&1), Some(&"one"));
+    assert_eq!(cache.put(3, "three"), Some((2, "two")));
+    assert!(cache.contains(&1));
+    assert!(!cache.contains(&2));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn peek_does_not_touch() {
+    let mut cache = Cache::with_algo(Algos::LRU, 2, None).unwrap();
+    cache.put(1, 10);
+    cache.put(2, 20);
+    assert_eq!(cache.peek(&1), Some(&10));
+    assert_eq!(cache.put(3, 30), Some((1, 10)));
+}
+
+#[test]
+fn get_mut_replace_remove_clear() {
+    let mut cache = Cache::with_algo(Algos::FIFO, 3, None).unwrap();
+    cache.put("a", 1);
+    cache.put("b", 2);
+    *cache.get_mut(&"a").unwrap() += 10;
+    assert_eq!(cache.put("b", 3), None);
+    assert_eq!(cache.peek(&"a"), Some(&11));
+    assert_eq!(cache.peek(&"b"), Some(&3));
+
+    let mut entries: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+    entries.sort();
+    assert_eq!(entries, vec![("a", 11), ("b", 3)]);
+
+    assert_eq!(cache.remove(&"a"), Some(11));
+    assert_eq!(cache.remove(&"a"), None);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&"b"), None);
+}
+
+#[test]
+fn same_hits_as_simulation() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(50)
+        .with_all_random()
+        .with_range_random(0, 40)
+        .build();
+
+    for algo in [Algos::FIFO, Algos::LRU, Algos::LFU, Algos::MRU, Algos::SLRU].iter() {
+        let expected = cache_algos::init(*algo, 16, None).run(&ram);
+
+        let mut cache = Cache::with_algo(*algo, 16, None).unwrap();
+        let mut statistic = (0, 0);
+        for batch in &ram {
+            for elem in batch {
+                if cache.get(elem).is_some() {
+                    statistic.0 += 1;
+                } else {
+                    statistic.1 += 1;
+                    cache.put(*elem, *elem);
+                }
+            }
+        }
+        assert_eq!(statistic, expected, "{}", algo);
+    }
+}
+
+#[test]
+fn unsupported_algorithms() {
+    for algo in [Algos::Belady, Algos::PLRU, Algos::BitPLRU, Algos::ClockPro, Algos::GDSF].iter() {
+        assert!(Cache::<i32, i32>::with_algo(*algo, 16, None).is_err(), "{}", algo);
+    }
+    assert!(Cache::<i32, i32>::with_algo(Algos::LRU, 16, None).is_ok());
+}
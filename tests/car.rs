@@ -0,0 +1,85 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::car::{CARCache, CARTCache};
+use cache_algos::cache::Policy;
+use cache_algos::lru::LRUCache;
+
+#[test]
+fn frequent_elements_survive_scan() {
+    // `1` and `2` are referenced, so the hand moves them out of the way of the scan of `3..8`.
+    let ram = vec![vec![1, 2, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2]];
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (2, 10));
+    assert_eq!(CARCache::new(4, None).run(&ram), (4, 8));
+    assert_eq!(CARTCache::new(4, None).run(&ram), (4, 8));
+}
+
+#[test]
+fn p_is_adapted() {
+    let mut car_cache = CARCache::new(4, None);
+    // `2` is discarded to B1 by `5`, then it is requested again.
+    assert_eq!(car_cache.run(&vec![vec![1, 2, 1, 3, 4, 5, 2]]), (1, 6));
+    assert_eq!(car_cache.p(), 1);
+    assert!(car_cache.contains(&2));
+    assert!(!car_cache.contains(&3));
+
+    car_cache.reset();
+    assert_eq!(car_cache.p(), 0);
+    assert!(car_cache.is_empty());
+}
+
+#[test]
+fn evicted_elements() {
+    let mut cart_cache = CARTCache::new(2, None);
+    assert_eq!(cart_cache.insert(1), None);
+    assert_eq!(cart_cache.insert(2), None);
+    cart_cache.touch(&1);
+    assert_eq!(cart_cache.insert(3), Some(2));
+    assert!(cart_cache.remove(&1));
+    assert!(!cart_cache.remove(&2));
+    assert_eq!(cart_cache.len(), 1);
+}
+
+#[test]
+fn hand_skips_referenced_page() {
+    let mut car_cache = CARCache::new(3, None);
+    for key in 1..4 {
+        assert_eq!(car_cache.insert(key), None);
+    }
+    car_cache.touch(&1);
+    // `1` is referenced, so it is moved to T2 and `2` is discarded.
+    assert_eq!(car_cache.insert(4), Some(2));
+    assert_eq!(car_cache.insert(5), Some(3));
+    assert!(car_cache.contains(&1));
+}
+
+#[test]
+fn p_and_q_are_bounded() {
+    let ram = common::random_ram(13);
+    for size in &common::SIZES {
+        let mut car_cache = CARCache::new(*size, None);
+        common::check_policy(&mut car_cache, &ram, |cache| assert!(cache.p() <= *size));
+        let mut cart_cache = CARTCache::new(*size, None);
+        common::check_policy(&mut cart_cache, &ram, |cache| {
+            assert!(cache.p() <= *size);
+            assert!(cache.q() <= 2 * *size);
+        });
+    }
+}
+
+#[test]
+fn promotion_to_long_term_keeps_q() {
+    let mut cart_cache = CARTCache::new(4, None);
+    // Long-term `8`, `0` and `5` are moved from T1 to T2 while making room for `4`, so `q` is set
+    // to size of the cache without T1.
+    assert_eq!(cart_cache.run(&vec![vec![8, 0, 6, 5, 5, 2, 8, 0, 7, 4]]), (1, 9));
+    assert_eq!(cart_cache.q(), 3);
+    // Referenced short-term `7` becomes long-term while making room for `6`, it stays in T1, so
+    // `q` is not changed.
+    cart_cache.access(&7);
+    cart_cache.access(&6);
+    assert_eq!(cart_cache.q(), 3);
+}
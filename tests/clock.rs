@@ -0,0 +1,84 @@
+extern crate cache_algos;
+
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::cache::Policy;
+use cache_algos::clock::ClockCache;
+use cache_algos::fifo::FifoCache;
+use cache_algos::gclock::GClockCache;
+use cache_algos::lru::LRUCache;
+use cache_algos::memory::RamBuilder;
+
+#[test]
+fn second_chance() {
+    // `4` clears all bits and replaces `1`, then `2` is requested, so the hand skips it.
+    let ram = vec![vec![1, 2, 3, 4, 2, 5, 2]];
+
+    assert_eq!(FifoCache::new(3, None).run(&ram), (1, 6));
+    assert_eq!(LRUCache::new(3, None).run(&ram), (2, 5));
+    assert_eq!(ClockCache::new(3, None).run(&ram), (2, 5));
+}
+
+#[test]
+fn counters_keep_frequent_element() {
+    // Counter of `1` survives the first turn of the hand.
+    let ram = vec![vec![1, 1, 1, 2, 3, 4, 5, 1]];
+
+    assert_eq!(ClockCache::new(3, None).run(&ram), (2, 6));
+    assert_eq!(GClockCache::new(3, None).run(&ram), (3, 5));
+}
+
+#[test]
+fn saturated_counter_is_kept() {
+    let mut gclock_cache = GClockCache::new(2, None).with_bits(8);
+    gclock_cache.insert(1);
+    for _ in 0..300 {
+        gclock_cache.touch(&1);
+    }
+    gclock_cache.insert(2);
+    // Counter of `1` stays at the maximum, so the hand stops at `2`.
+    assert_eq!(gclock_cache.insert(3), Some(2));
+    assert!(gclock_cache.contains(&1));
+}
+
+#[test]
+fn one_bit_gclock_same_as_clock() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 30)
+        .with_seed(3)
+        .build();
+
+    for size in &[1, 2, 10, 25] {
+        let mut clock_cache = ClockCache::new(*size, None);
+        let mut gclock_cache = GClockCache::new(*size, None).with_bits(1);
+        for elem in ram.iter().flat_map(|batch| batch.iter()) {
+            assert_eq!(gclock_cache.access(elem), clock_cache.access(elem));
+            assert!(clock_cache.len() <= *size);
+        }
+    }
+}
+
+#[test]
+fn removed_slot_is_reused() {
+    let mut clock_cache = ClockCache::new(2, None);
+    assert_eq!(clock_cache.insert(1), None);
+    assert_eq!(clock_cache.insert(2), None);
+    assert!(clock_cache.remove(&1));
+    assert!(!clock_cache.remove(&1));
+    assert_eq!(clock_cache.insert(3), None);
+    // All bits are set, so the hand makes full turn and stops at the first slot.
+    assert_eq!(clock_cache.insert(4), Some(3));
+    assert_eq!(clock_cache.len(), 2);
+}
+
+#[test]
+fn zero_size() {
+    let ram = vec![vec![1, 2, 2, 1]];
+
+    let expected = LRUCache::new(0, None).run(&ram);
+    for algo in &[Algos::CLOCK, Algos::GCLOCK, Algos::NRU] {
+        assert_eq!(cache_algos::init(*algo, 0, None).run(&ram), expected);
+    }
+}
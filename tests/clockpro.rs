@@ -0,0 +1,41 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::clockpro::ClockProCache;
+use cache_algos::lru::LRUCache;
+
+#[test]
+fn loop_greater_than_cache() {
+    // LRU discards every element right before its request, CLOCK-Pro keeps some hot pages.
+    let ram: Vec<Vec<i32>> = (0..10).map(|_| (0..5).collect()).collect();
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (0, 50));
+    assert_eq!(ClockProCache::new(4, None).run(&ram), (6, 44));
+}
+
+#[test]
+fn hot_pages_survive_scan() {
+    // `1` and `2` become hot, the scan of `3..10` goes through cold pages.
+    let ram = vec![vec![1, 2, 1, 2, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 1, 2]];
+
+    assert_eq!(LRUCache::new(6, None).run(&ram), (4, 12));
+    assert_eq!(ClockProCache::new(6, None).run(&ram), (6, 10));
+}
+
+#[test]
+fn cold_target_is_bounded() {
+    for seed in 0..10 {
+        let ram = common::random_ram(seed);
+        for size in &[1, 2, 3, 10, 25] {
+            let mut clockpro_cache = ClockProCache::new(*size, None);
+            common::check_size(&mut clockpro_cache, &ram, |cache| {
+                assert!(cache.cold_target() >= 1);
+                assert!(cache.cold_target() <= *size);
+            });
+            clockpro_cache.reset();
+            assert_eq!(clockpro_cache.run(&ram), ClockProCache::new(*size, None).run(&ram));
+        }
+    }
+}
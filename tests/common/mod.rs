@@ -0,0 +1,49 @@
+//! Helpers, which are shared by tests.
+
+#![allow(dead_code)]
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::memory::RamBuilder;
+
+/// Sizes of caches, which are checked on random RAM.
+pub const SIZES: [usize; 4] = [1, 2, 10, 25];
+
+/// RAM of 20 batches of 100 random elements in `0..30` range, generated with the seed.
+pub fn random_ram(seed: usize) -> Vec<Vec<i32>> {
+    RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 30)
+        .with_seed(seed)
+        .build()
+}
+
+/// Access every element of the RAM and check, that the cache never has more elements than its
+/// capacity. `check` is called after every access.
+pub fn check_size<C, F>(cache: &mut C, ram: &Vec<Vec<i32>>, mut check: F)
+    where C: CacheAlgorithm<i32> + ?Sized,
+          F: FnMut(&C)
+{
+    for elem in ram.iter().flat_map(|batch| batch.iter()) {
+        cache.access(elem);
+        assert!(cache.len() <= cache.capacity());
+        check(cache);
+    }
+}
+
+/// Same as `check_size`, also checks, that `access` returns `true` only for elements of the cache
+/// and the accessed element is put into the cache.
+pub fn check_policy<P, F>(cache: &mut P, ram: &Vec<Vec<i32>>, mut check: F)
+    where P: Policy<i32> + ?Sized,
+          F: FnMut(&P)
+{
+    for elem in ram.iter().flat_map(|batch| batch.iter()) {
+        let contains = cache.contains(elem);
+        assert_eq!(cache.access(elem), contains);
+        assert!(cache.contains(elem));
+        assert!(cache.len() <= cache.capacity());
+        check(cache);
+    }
+}
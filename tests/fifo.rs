@@ -1,5 +1,6 @@
 extern crate cache_algos;
 
+use cache_algos::CacheAlgorithm;
 use cache_algos::memory::RamBuilder;
 use cache_algos::fifo::FifoCache;
 
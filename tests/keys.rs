@@ -0,0 +1,59 @@
+extern crate cache_algos;
+
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::lru::LRUCache;
+use cache_algos::memory::RamBuilder;
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+
+#[test]
+fn u64_keys() {
+    let ram: Vec<Vec<u64>> = vec![vec![1 << 40, 1 << 41, 1 << 40, 1 << 42, 1 << 41]];
+
+    let mut lru_cache = LRUCache::new(2, None);
+    assert_eq!(lru_cache.run(&ram), (1, 4));
+}
+
+#[test]
+fn string_keys() {
+    let ram: Vec<Vec<String>> = vec!["/index.html", "/logo.png", "/index.html", "/about.html"]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .chunks(2)
+        .map(|batch| batch.to_vec())
+        .collect();
+
+    for algo in [Algos::FIFO, Algos::LRU, Algos::LFU, Algos::MRU, Algos::SLRU].iter() {
+        let mut cache = cache_algos::init::<String>(*algo, 2, None);
+        assert_eq!(cache.run(&ram), (1, 3));
+    }
+}
+
+#[test]
+fn ram_from_lines() {
+    let path = env::temp_dir().join("cache_algos_ram_from_lines.txt");
+    {
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"18446744073709551615\n0\n\n42\n").unwrap();
+    }
+
+    let ram: Vec<Vec<u64>> = RamBuilder::from_lines(None, path.to_str().unwrap());
+    assert_eq!(ram, vec![vec![u64::max_value(), 0, 42]]);
+}
+
+#[test]
+fn ram_from_file() {
+    let path = env::temp_dir().join("cache_algos_ram_from_file.bin");
+    {
+        let mut file = File::create(&path).unwrap();
+        file.write_all(&[0, 255, 42]).unwrap();
+    }
+
+    let ram: Vec<Vec<u64>> = RamBuilder::from_file(None, path.to_str().unwrap());
+    assert_eq!(ram, vec![vec![0, 255, 42]]);
+    let ram: Vec<Vec<i32>> = RamBuilder::from_file(None, path.to_str().unwrap());
+    assert_eq!(ram, vec![vec![0, 255, 42]]);
+}
@@ -0,0 +1,74 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::cache::Policy;
+use cache_algos::lecar::LeCaRCache;
+use cache_algos::lfu::LFUCache;
+use cache_algos::lru::LRUCache;
+
+// Hot elements are mixed with scans, LFU keeps hot elements.
+fn frequency_phase() -> Vec<Vec<i32>> {
+    (0..50).map(|i| (0..5).chain(0..5).chain(1000 + 8 * i..1008 + 8 * i).collect()).collect()
+}
+
+// Working set changes, LFU keeps old elements.
+fn recency_phase() -> Vec<Vec<i32>> {
+    (0..50)
+        .map(|i| (0..4).flat_map(|_| 100_000 + 8 * i..100_008 + 8 * i).collect())
+        .collect()
+}
+
+#[test]
+fn experts() {
+    let mut lru_cache = LRUCache::new(10, None);
+    assert_eq!(lru_cache.run(&frequency_phase()), (250, 650));
+    assert_eq!(lru_cache.run(&recency_phase()), (1200, 400));
+    let mut lfu_cache = LFUCache::new(10, None);
+    assert_eq!(lfu_cache.run(&frequency_phase()), (495, 405));
+    assert_eq!(lfu_cache.run(&recency_phase()), (0, 1600));
+}
+
+#[test]
+fn weights_follow_workload() {
+    // Experts are chosen randomly, so results are compared with results of the best expert.
+    for seed in 0..5 {
+        let mut lecar_cache = LeCaRCache::with_seed(10, seed, None);
+        assert_eq!(lecar_cache.weights(), (0.5, 0.5));
+        assert!(lecar_cache.run(&frequency_phase()).0 > 450);
+        assert!(lecar_cache.weights().1 > 0.9);
+        assert!(lecar_cache.run(&recency_phase()).0 > 1000);
+        assert!(lecar_cache.weights().0 > 0.9);
+        lecar_cache.reset();
+        assert_eq!(lecar_cache.weights(), (0.5, 0.5));
+    }
+}
+
+#[test]
+fn same_seed_same_result() {
+    let ram = common::random_ram(24);
+    for size in &common::SIZES {
+        let expected = LeCaRCache::with_seed(*size, 42, None).run(&ram);
+        let mut lecar_cache = cache_algos::init_with_seed(Algos::LeCaR, *size, 42, None);
+        assert_eq!(lecar_cache.run(&ram), expected);
+
+        let mut lecar_cache = LeCaRCache::with_seed(*size, 42, None).with_learning_rate(0.1);
+        common::check_policy(&mut lecar_cache, &ram, |_| {});
+    }
+}
+
+#[test]
+fn same_victim_is_not_remembered() {
+    let mut lecar_cache = LeCaRCache::with_seed(2, 0, None);
+    lecar_cache.insert(1);
+    lecar_cache.insert(2);
+    lecar_cache.touch(&2);
+    // `1` is the least recently and the least frequently used element.
+    assert_eq!(lecar_cache.insert(3), Some(1));
+    lecar_cache.insert(1);
+    assert_eq!(lecar_cache.weights(), (0.5, 0.5));
+    assert!(lecar_cache.remove(&1));
+    assert!(!lecar_cache.remove(&1));
+    assert_eq!(lecar_cache.len(), 1);
+}
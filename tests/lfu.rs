@@ -0,0 +1,56 @@
+extern crate cache_algos;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lfu::{LFUCache, TieBreak};
+use cache_algos::memory::RamBuilder;
+
+// Return element, which is discarded, when `1`, `2` and `3` have equal frequency.
+fn victim(tie_break: TieBreak) -> i32 {
+    let mut lfu_cache = LFUCache::new(3, None).with_tie_break(tie_break);
+    for elem in &[1, 2, 3, 3, 1, 2] {
+        lfu_cache.access(elem);
+    }
+    lfu_cache.insert(4).unwrap()
+}
+
+#[test]
+fn tie_break_lru() {
+    assert_eq!(victim(TieBreak::LRU), 3);
+}
+
+#[test]
+fn tie_break_mru() {
+    assert_eq!(victim(TieBreak::MRU), 2);
+}
+
+#[test]
+fn tie_break_fifo() {
+    // Elements entered the second frequency list in order `3, 1, 2`.
+    assert_eq!(victim(TieBreak::FIFO), 3);
+}
+
+#[test]
+fn check_simple_hit() {
+    let ram = vec![vec![1, 2, 1, 3, 2, 1]];
+
+    let mut lfu_cache = LFUCache::new(2, None);
+    assert_eq!(lfu_cache.run(&ram), (2, 4));
+}
+
+#[test]
+fn fifo_tie_break_same_as_lru() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(100)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 300)
+        .with_seed(6)
+        .build();
+
+    for size in &[2, 10, 64, 250, 400] {
+        let expected = LFUCache::new(*size, None).run(&ram);
+        let mut lfu_cache = LFUCache::new(*size, None).with_tie_break(TieBreak::FIFO);
+        assert_eq!(lfu_cache.run(&ram), expected);
+    }
+}
@@ -0,0 +1,91 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::cache::{Cache, Policy};
+use cache_algos::lfu::LFUCache;
+use cache_algos::lfuda::{GDSFCache, LFUDACache};
+
+#[test]
+fn old_element_is_aged_out() {
+    // `1` was hot long ago, LFU keeps it forever.
+    let ram = vec![vec![1; 5], (0..10).map(|i| 2 + i % 2).collect()];
+
+    assert_eq!(LFUCache::new(2, None).run(&ram), (4, 11));
+    assert_eq!(LFUDACache::new(2, None).run(&ram), (8, 7));
+}
+
+#[test]
+fn age_is_priority_of_evicted() {
+    let mut lfuda_cache = LFUDACache::new(2, None);
+    lfuda_cache.insert(1);
+    lfuda_cache.touch(&1);
+    lfuda_cache.touch(&1);
+    lfuda_cache.insert(2);
+    assert_eq!(lfuda_cache.victim(), Some(2));
+    assert_eq!(lfuda_cache.insert(3), Some(2));
+    assert_eq!(lfuda_cache.age(), 1.0);
+    // `3` has priority 2, `1` has priority 3.
+    assert_eq!(lfuda_cache.insert(4), Some(3));
+    assert_eq!(lfuda_cache.age(), 2.0);
+    // `4` has priority 3 and is put after `1`, so `1` is discarded.
+    assert_eq!(lfuda_cache.insert(5), Some(1));
+    assert!(lfuda_cache.remove(&4));
+    assert!(!lfuda_cache.remove(&4));
+    assert_eq!(lfuda_cache.len(), 1);
+}
+
+#[test]
+fn key_value_cache() {
+    let mut cache = Cache::with_algo(Algos::LFUDA, 2, None).unwrap();
+    cache.put(1, "one");
+    cache.put(2, "two");
+    cache.get(&2);
+    assert_eq!(cache.put(3, "three"), Some((1, "one")));
+}
+
+#[test]
+fn small_objects_are_preferred() {
+    let mut gdsf_cache = GDSFCache::new(10, None).with_size_of(Box::new(|&x: &i32| x as usize));
+    assert!(!gdsf_cache.access(&1));
+    assert!(!gdsf_cache.access(&2));
+    // `2` has priority 1/2, so it is discarded.
+    assert!(!gdsf_cache.access(&8));
+    assert!(gdsf_cache.contains(&1));
+    assert!(!gdsf_cache.contains(&2));
+    assert_eq!(gdsf_cache.len(), 9);
+    assert_eq!(gdsf_cache.age(), 0.5);
+    // `8` has priority 1/2 + 1/8, `1` has priority 1.
+    assert!(!gdsf_cache.access(&3));
+    assert!(!gdsf_cache.contains(&8));
+    assert!(gdsf_cache.access(&1));
+    assert_eq!(gdsf_cache.len(), 4);
+    // Too big object is not put into the cache.
+    assert!(!gdsf_cache.access(&11));
+    assert!(!gdsf_cache.contains(&11));
+    assert_eq!(gdsf_cache.len(), 4);
+}
+
+#[test]
+fn gdsf_with_unit_sizes_same_as_lfuda() {
+    let ram = common::random_ram(21);
+    for size in &common::SIZES {
+        let mut lfuda_cache = LFUDACache::new(*size, None);
+        let mut gdsf_cache = GDSFCache::new(*size, None);
+        for elem in ram.iter().flat_map(|batch| batch.iter()) {
+            assert_eq!(gdsf_cache.access(elem), lfuda_cache.access(elem));
+            assert!(lfuda_cache.len() <= *size);
+        }
+    }
+}
+
+#[test]
+fn gdsf_sizes_of_elements() {
+    let ram = common::random_ram(21);
+    for size in &[1, 10, 50] {
+        let mut gdsf_cache = GDSFCache::new(*size, None)
+            .with_size_of(Box::new(|&x: &i32| 1 + (x % 7) as usize));
+        common::check_size(&mut gdsf_cache, &ram, |_| ());
+    }
+}
@@ -0,0 +1,58 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lirs::LIRSCache;
+use cache_algos::lru::LRUCache;
+
+#[test]
+fn loop_greater_than_cache() {
+    // LRU discards every element right before its request, LIRS keeps LIR elements.
+    let ram: Vec<Vec<i32>> = (0..10).map(|_| (0..5).collect()).collect();
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (0, 50));
+    assert_eq!(LIRSCache::new(4, None).run(&ram), (27, 23));
+}
+
+#[test]
+fn hir_element_becomes_lir() {
+    let mut lirs_cache = LIRSCache::new(3, None);
+    // `1` and `2` are LIR, `3` and `4` are HIR, `3` is discarded, but it is still in the stack.
+    for key in 1..5 {
+        lirs_cache.access(&key);
+    }
+    assert!(!lirs_cache.contains(&3));
+    // `3` has smaller reuse distance than `1`, so `1` becomes HIR and is discarded before `2`.
+    assert_eq!(lirs_cache.insert(3), Some(4));
+    assert_eq!(lirs_cache.insert(5), Some(1));
+    assert_eq!(lirs_cache.insert(6), Some(5));
+    assert!(lirs_cache.contains(&2));
+    assert!(lirs_cache.contains(&3));
+}
+
+#[test]
+fn lir_elements_survive_scan() {
+    // `1` and `2` are LIR, the scan of `3..8` goes through the only HIR resident place.
+    let ram = vec![vec![1, 2, 1, 2, 3, 4, 5, 6, 7, 1, 2]];
+
+    assert_eq!(LRUCache::new(3, None).run(&ram), (2, 9));
+    assert_eq!(LIRSCache::new(3, None).run(&ram), (4, 7));
+}
+
+#[test]
+fn any_hir_fraction() {
+    let ram = common::random_ram(15);
+    for &hir in &[0.01, 0.25, 1.0] {
+        for size in &common::SIZES {
+            let mut lirs_cache = LIRSCache::new(*size, None).with_hir(hir);
+            common::check_policy(&mut lirs_cache, &ram, |_| ());
+            for elem in ram[0].iter() {
+                let contains = lirs_cache.contains(elem);
+                assert_eq!(lirs_cache.remove(elem), contains);
+                assert!(!lirs_cache.contains(elem));
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lfu::LFUCache;
+use cache_algos::lrfu::LRFUCache;
+use cache_algos::lru::LRUCache;
+use cache_algos::wlfu::WLFUCache;
+
+#[test]
+fn lambda_bounds() {
+    let ram = common::random_ram(22);
+    for size in &common::SIZES {
+        let mut lru_cache = LRUCache::new(*size, None);
+        let mut lfu_cache = LFUCache::new(*size, None);
+        let mut lrfu_lru = LRFUCache::new(*size, None).with_lambda(1.0);
+        let mut lrfu_lfu = LRFUCache::new(*size, None).with_lambda(0.0);
+        for elem in ram.iter().flat_map(|batch| batch.iter()) {
+            assert_eq!(lrfu_lru.access(elem), lru_cache.access(elem));
+            assert_eq!(lrfu_lfu.access(elem), lfu_cache.access(elem));
+        }
+    }
+}
+
+#[test]
+fn crf_decays() {
+    let mut lrfu_cache = LRFUCache::new(2, None).with_lambda(0.5);
+    lrfu_cache.insert(1);
+    assert_eq!(lrfu_cache.crf(&1), Some(1.0));
+    lrfu_cache.insert(2);
+    assert_eq!(lrfu_cache.crf(&1), Some(0.5f64.sqrt()));
+    lrfu_cache.touch(&1);
+    assert_eq!(lrfu_cache.crf(&1), Some(1.5));
+    assert_eq!(lrfu_cache.crf(&3), None);
+    // CRF of `2` is 0.5, so it is discarded.
+    assert_eq!(lrfu_cache.victim(), Some(2));
+    assert_eq!(lrfu_cache.insert(3), Some(2));
+}
+
+// Return element, which is discarded by `3` after requests `1, 1, 1, 2, 2`.
+fn wlfu_victim(window: usize) -> Option<i32> {
+    let mut wlfu_cache = WLFUCache::new(2, None).with_window(window);
+    for elem in &[1, 1, 1, 2, 2] {
+        wlfu_cache.access(elem);
+    }
+    wlfu_cache.insert(3)
+}
+
+#[test]
+fn window_boundary() {
+    // All requests are in the window, `2` has less requests than `1`.
+    assert_eq!(wlfu_victim(6), Some(2));
+    // The request of `3` moves the first request of `1` out of the window, `1` and `2` have two
+    // requests, `1` is the least recently used.
+    assert_eq!(wlfu_victim(5), Some(1));
+}
+
+#[test]
+fn window_frequency() {
+    let mut wlfu_cache = WLFUCache::new(2, None).with_window(3);
+    wlfu_cache.insert(1);
+    wlfu_cache.touch(&1);
+    wlfu_cache.insert(2);
+    assert_eq!(wlfu_cache.frequency(&1), 2);
+    // The first request of `1` leaves the window, `2` and `1` have one request.
+    wlfu_cache.touch(&2);
+    assert_eq!(wlfu_cache.frequency(&1), 1);
+    assert_eq!(wlfu_cache.frequency(&2), 2);
+    assert_eq!(wlfu_cache.victim(), Some(1));
+    assert_eq!(wlfu_cache.insert(3), Some(1));
+    assert!(wlfu_cache.remove(&3));
+    assert!(!wlfu_cache.remove(&3));
+    assert_eq!(wlfu_cache.len(), 1);
+}
+
+#[test]
+fn any_lambda_and_window() {
+    let ram = common::random_ram(22);
+    for size in &common::SIZES {
+        for lambda in &[0.0, 0.001, 0.1, 0.5, 1.0] {
+            let mut lrfu_cache = LRFUCache::new(*size, None).with_lambda(*lambda);
+            common::check_policy(&mut lrfu_cache, &ram, |_| ());
+        }
+        for window in &[1, *size, 10 * *size] {
+            let mut wlfu_cache = WLFUCache::new(*size, None).with_window(*window);
+            common::check_policy(&mut wlfu_cache, &ram, |_| ());
+        }
+    }
+}
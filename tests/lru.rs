@@ -0,0 +1,77 @@
+extern crate cache_algos;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::lru::LRUCache;
+use cache_algos::memory::RamBuilder;
+
+use std::collections::VecDeque;
+
+/// Previous implementation of LRU, which searches element in the queue.
+struct NaiveLRU {
+    cache: VecDeque<i32>,
+    size: usize,
+}
+
+impl NaiveLRU {
+    fn hit(&mut self, val: &i32) -> bool {
+        if let Some(position) = self.cache.iter().position(|x| x == val) {
+            let elem = self.cache.remove(position).unwrap();
+            self.cache.push_front(elem);
+            true
+        } else {
+            if self.cache.len() < self.size {
+                self.cache.push_front(val.clone());
+            } else {
+                self.cache.pop_back();
+                self.cache.push_front(val.clone());
+            }
+            false
+        }
+    }
+}
+
+fn check_same_as_naive(ram: &Vec<Vec<i32>>, size: usize) {
+    let mut naive = NaiveLRU {
+        cache: VecDeque::new(),
+        size: size,
+    };
+    let mut lru_cache = LRUCache::new(size, None);
+    for elem in ram.iter().flat_map(|batch| batch.iter()) {
+        assert_eq!(lru_cache.access(elem), naive.hit(elem));
+        assert_eq!(lru_cache.len(), naive.cache.len());
+    }
+}
+
+#[test]
+fn check_simple_hit() {
+    let ram = vec![vec![1, 2, 1, 3, 2, 1]];
+
+    let mut lru_cache = LRUCache::new(2, None);
+    assert_eq!(lru_cache.run(&ram), (1, 5));
+}
+
+#[test]
+fn same_as_naive_on_direct_ram() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(50)
+        .with_size_batch(100)
+        .build();
+
+    for size in &[2, 50, 99, 100, 150] {
+        check_same_as_naive(&ram, *size);
+    }
+}
+
+#[test]
+fn same_as_naive_on_random_ram() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(100)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 300)
+        .build();
+
+    for size in &[2, 10, 64, 250, 400] {
+        check_same_as_naive(&ram, *size);
+    }
+}
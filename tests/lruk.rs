@@ -0,0 +1,81 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lru::LRUCache;
+use cache_algos::lruk::LRUKCache;
+
+#[test]
+fn lru1_same_as_lru() {
+    let ram = common::random_ram(20);
+    for size in &common::SIZES {
+        let mut lru_cache = LRUCache::new(*size, None);
+        let mut lruk_cache = LRUKCache::new(*size, None).with_k(1);
+        for elem in ram.iter().flat_map(|batch| batch.iter()) {
+            assert_eq!(lruk_cache.access(elem), lru_cache.access(elem));
+        }
+    }
+}
+
+#[test]
+fn scan_resistance() {
+    // `3`, `4` and `5` are requested once, so they are discarded before `1` and `2`.
+    let ram = vec![vec![1, 1, 2, 2, 3, 4, 5, 1, 2]];
+
+    assert_eq!(LRUCache::new(3, None).run(&ram), (2, 7));
+    assert_eq!(LRUKCache::new(3, None).run(&ram), (4, 5));
+}
+
+#[test]
+fn correlated_requests() {
+    let mut lruk_cache = LRUKCache::new(2, None);
+    lruk_cache.insert(1);
+    lruk_cache.touch(&1);
+    lruk_cache.insert(2);
+    assert_eq!(lruk_cache.insert(3), Some(2));
+
+    // The second request of `1` is correlated, so `1` has only one request. `2` is in the
+    // correlated period, so it can't be discarded.
+    let mut lruk_cache = LRUKCache::new(2, None).with_correlated_period(1);
+    lruk_cache.insert(1);
+    lruk_cache.touch(&1);
+    lruk_cache.insert(2);
+    assert_eq!(lruk_cache.victim(), Some(1));
+    assert_eq!(lruk_cache.insert(3), Some(1));
+}
+
+#[test]
+fn retained_history() {
+    for &(period, evicted) in &[(100, 4), (2, 1), (0, 1)] {
+        let mut lruk_cache = LRUKCache::new(2, None).with_retained_period(period);
+        lruk_cache.insert(1);
+        lruk_cache.insert(2);
+        assert_eq!(lruk_cache.insert(3), Some(1));
+        assert_eq!(lruk_cache.insert(1), Some(2));
+        assert_eq!(lruk_cache.insert(4), Some(3));
+        // With retained history `1` has two requests.
+        assert_eq!(lruk_cache.insert(5), Some(evicted));
+    }
+}
+
+#[test]
+fn victim_is_evicted() {
+    let ram = common::random_ram(20);
+    for size in &common::SIZES {
+        let mut lruk_cache = LRUKCache::new(*size, None)
+            .with_k(3)
+            .with_correlated_period(3)
+            .with_retained_period(50);
+        for elem in ram.iter().flat_map(|batch| batch.iter()) {
+            if lruk_cache.contains(elem) {
+                lruk_cache.touch(elem);
+            } else {
+                let victim = lruk_cache.victim();
+                assert_eq!(lruk_cache.insert(*elem), victim);
+            }
+            assert!(lruk_cache.len() <= *size);
+        }
+    }
+}
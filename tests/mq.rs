@@ -0,0 +1,69 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lru::LRUCache;
+use cache_algos::mq::MQCache;
+
+#[test]
+fn frequent_element_survives() {
+    // `1` is in the third queue, so `2` and `3` are discarded from the first queue.
+    let ram = vec![vec![1, 1, 1, 1, 2, 3, 4, 1]];
+
+    assert_eq!(LRUCache::new(2, None).run(&ram), (3, 5));
+    assert_eq!(MQCache::new(2, None).run(&ram), (4, 4));
+}
+
+fn frequency_is_remembered(history: usize) -> Option<i32> {
+    let mut mq_cache = MQCache::new(2, None).with_life_time(100).with_history(history);
+    mq_cache.insert(1);
+    mq_cache.touch(&1);
+    mq_cache.insert(2);
+    mq_cache.touch(&2);
+    // The first queue is empty, so `1` is discarded from the second queue.
+    assert_eq!(mq_cache.insert(3), Some(1));
+    assert_eq!(mq_cache.insert(1), Some(3));
+    mq_cache.insert(4)
+}
+
+#[test]
+fn history() {
+    // `1` gets its frequency from Qout and goes to the second queue.
+    assert_eq!(frequency_is_remembered(8), Some(2));
+    assert_eq!(frequency_is_remembered(0), Some(1));
+}
+
+#[test]
+fn expired_element_is_demoted() {
+    let mut mq_cache = MQCache::new(2, None).with_life_time(1);
+    mq_cache.insert(1);
+    mq_cache.touch(&1);
+    mq_cache.insert(2);
+    assert_eq!(mq_cache.insert(3), Some(2));
+    // `1` was not requested during its life time, so it was moved to the first queue after `3`.
+    assert_eq!(mq_cache.insert(4), Some(3));
+    assert_eq!(mq_cache.insert(5), Some(1));
+}
+
+#[test]
+fn one_queue_same_as_lru() {
+    // Without history and other queues MQ is LRU.
+    let ram = common::random_ram(14);
+    for size in &common::SIZES {
+        let expected = LRUCache::new(*size, None).run(&ram);
+        assert_eq!(MQCache::new(*size, None).with_queues(1).with_history(0).run(&ram), expected);
+    }
+}
+
+#[test]
+fn any_count_of_queues() {
+    let ram = common::random_ram(14);
+    for &queues in &[1, 2, 8] {
+        for size in &common::SIZES {
+            let mut mq_cache = MQCache::new(*size, None).with_queues(queues);
+            common::check_policy(&mut mq_cache, &ram, |_| ());
+        }
+    }
+}
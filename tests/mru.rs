@@ -0,0 +1,75 @@
+extern crate cache_algos;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::mru::MRUCache;
+use cache_algos::memory::RamBuilder;
+
+/// Previous implementation of MRU, which searches element in the vector.
+struct NaiveMRU {
+    cache: Vec<i32>,
+    size: usize,
+}
+
+impl NaiveMRU {
+    fn hit(&mut self, val: &i32) -> bool {
+        if let Some(position) = self.cache.iter().position(|x| x == val) {
+            let elem = self.cache.remove(position);
+            self.cache.push(elem);
+            true
+        } else {
+            if self.cache.len() < self.size {
+                self.cache.push(val.clone());
+            } else {
+                self.cache.pop();
+                self.cache.push(val.clone());
+            }
+            false
+        }
+    }
+}
+
+fn check_same_as_naive(ram: &Vec<Vec<i32>>, size: usize) {
+    let mut naive = NaiveMRU {
+        cache: Vec::new(),
+        size: size,
+    };
+    let mut mru_cache = MRUCache::new(size, None);
+    for elem in ram.iter().flat_map(|batch| batch.iter()) {
+        assert_eq!(mru_cache.access(elem), naive.hit(elem));
+        assert_eq!(mru_cache.len(), naive.cache.len());
+    }
+}
+
+#[test]
+fn check_simple_hit() {
+    let ram = vec![vec![1, 2, 1, 3, 2, 1]];
+
+    let mut mru_cache = MRUCache::new(2, None);
+    assert_eq!(mru_cache.run(&ram), (2, 4));
+}
+
+#[test]
+fn same_as_naive_on_direct_ram() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(50)
+        .with_size_batch(100)
+        .build();
+
+    for size in &[2, 50, 99, 100, 150] {
+        check_same_as_naive(&ram, *size);
+    }
+}
+
+#[test]
+fn same_as_naive_on_random_ram() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(100)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 300)
+        .build();
+
+    for size in &[2, 10, 64, 250, 400] {
+        check_same_as_naive(&ram, *size);
+    }
+}
@@ -0,0 +1,45 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::cache::Policy;
+use cache_algos::nru::NRUCache;
+
+fn victim(mut nru_cache: NRUCache<i32>) -> Option<i32> {
+    nru_cache.insert(1);
+    nru_cache.insert(2);
+    nru_cache.insert(3);
+    nru_cache.touch(&1);
+    nru_cache.insert(4)
+}
+
+#[test]
+fn bits_are_cleared_periodically() {
+    // Bits are cleared on the third request, then `1` is requested again.
+    assert_eq!(victim(NRUCache::new(3, None)), Some(2));
+    // Bits are never cleared, so the first element is discarded.
+    assert_eq!(victim(NRUCache::new(3, None).with_period(100)), Some(1));
+}
+
+#[test]
+fn referenced_elements_are_skipped() {
+    let mut nru_cache = NRUCache::new(3, None).with_period(4);
+    nru_cache.insert(1);
+    nru_cache.insert(2);
+    nru_cache.insert(3);
+    // Bits are cleared on the fourth request, then `1` and `3` are requested.
+    nru_cache.touch(&1);
+    nru_cache.touch(&3);
+    assert_eq!(nru_cache.insert(4), Some(2));
+}
+
+#[test]
+fn any_period() {
+    let ram = common::random_ram(16);
+    for &period in &[1, 10, 1000] {
+        for size in &common::SIZES {
+            let mut nru_cache = NRUCache::new(*size, None).with_period(period);
+            common::check_policy(&mut nru_cache, &ram, |_| ());
+        }
+    }
+}
@@ -0,0 +1,70 @@
+extern crate cache_algos;
+
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::lru::LRUCache;
+use cache_algos::memory::RamBuilder;
+use cache_algos::plru::{BitPLRUCache, TreePLRUCache};
+
+#[test]
+fn tree_plru_diverges_from_lru() {
+    // After `0` is used again, the tree points to the pair `2, 3` and then to `2`, while `1` is
+    // the least recently used element.
+    let ram = vec![vec![0, 1, 2, 3, 0, 4, 1]];
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (1, 6));
+    assert_eq!(TreePLRUCache::new(4, None).run(&ram), (2, 5));
+}
+
+#[test]
+fn bit_plru_diverges_from_lru() {
+    // Access to `2` sets the last bit, so all bits except the bit of `2` are cleared and `0` is
+    // discarded, while `3` is the least recently used element.
+    let ram = vec![vec![0, 1, 2, 3, 0, 1, 2, 4, 3]];
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (3, 6));
+    assert_eq!(BitPLRUCache::new(4, None).run(&ram), (4, 5));
+}
+
+#[test]
+fn two_ways_same_as_lru() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 5)
+        .with_seed(1)
+        .build();
+
+    let expected = LRUCache::new(2, None).run(&ram);
+    assert_eq!(TreePLRUCache::new(2, None).run(&ram), expected);
+    assert_eq!(BitPLRUCache::new(2, None).run(&ram), expected);
+}
+
+#[test]
+fn size_not_power_of_two() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(20)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 10)
+        .with_seed(2)
+        .build();
+
+    let mut plru_cache = TreePLRUCache::new(6, None);
+    for elem in ram.iter().flat_map(|batch| batch.iter()) {
+        plru_cache.access(elem);
+        assert!(plru_cache.len() <= 6);
+    }
+    assert_eq!(TreePLRUCache::new(6, None).run(&vec![(0..6).collect(), (0..6).collect()]),
+               (6, 6));
+}
+
+#[test]
+fn zero_size() {
+    let ram = vec![vec![1, 2, 2, 1]];
+
+    let expected = LRUCache::new(0, None).run(&ram);
+    for algo in &[Algos::PLRU, Algos::BitPLRU] {
+        assert_eq!(cache_algos::init(*algo, 0, None).run(&ram), expected);
+    }
+}
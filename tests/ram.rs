@@ -34,3 +34,19 @@ fn ram_with_43_batches_4_elements() {
         assert_eq!(batch.len(), 4);
     }
 }
+
+#[test]
+fn ram_with_seed() {
+    let build = |seed| {
+        RamBuilder::new(None)
+            .with_count_batches(10)
+            .with_size_batch(10)
+            .with_all_random()
+            .with_range_random(0, 1000)
+            .with_seed(seed)
+            .build()
+    };
+
+    assert_eq!(build(1), build(1));
+    assert!(build(1) != build(2));
+}
@@ -0,0 +1,81 @@
+extern crate cache_algos;
+
+use cache_algos::{Algos, CacheAlgorithm};
+use cache_algos::cache::Policy;
+use cache_algos::memory::RamBuilder;
+use cache_algos::rr::RRCache;
+
+#[test]
+fn check_normal_hit() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(100)
+        .with_size_batch(100)
+        .build();
+
+    let mut rr_cache = RRCache::new(150, None);
+    assert_eq!(rr_cache.run(&ram), (9900, 100));
+}
+
+#[test]
+fn index_follows_swapped_elements() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(50)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 100)
+        .build();
+
+    let mut rr_cache = RRCache::new(10, None);
+    for elem in ram.iter().flat_map(|batch| batch.iter()) {
+        rr_cache.access(elem);
+        assert!(rr_cache.len() <= 10);
+        assert!(rr_cache.contains(elem));
+    }
+    let resident: Vec<i32> = (0..100).filter(|x| rr_cache.contains(x)).collect();
+    assert_eq!(resident.len(), 10);
+    for elem in &resident {
+        assert!(rr_cache.remove(elem));
+        assert!(!rr_cache.contains(elem));
+    }
+    assert!(rr_cache.is_empty());
+}
+
+#[test]
+fn same_seed_same_result() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(50)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 100)
+        .with_seed(42)
+        .build();
+
+    let result = RRCache::with_seed(10, 7, None).run(&ram);
+    for _ in 0..5 {
+        let mut rr_cache = RRCache::with_seed(10, 7, None);
+        assert_eq!(rr_cache.seed(), Some(7));
+        assert_eq!(rr_cache.run(&ram), result);
+    }
+}
+
+#[test]
+fn fixed_seed_result() {
+    let ram = RamBuilder::new(None)
+        .with_count_batches(50)
+        .with_size_batch(100)
+        .with_all_random()
+        .with_range_random(0, 100)
+        .with_seed(42)
+        .build();
+
+    let mut rr_cache = RRCache::with_seed(10, 7, None);
+    assert_eq!(rr_cache.run(&ram), (496, 4504));
+}
+
+#[test]
+fn zero_size() {
+    let mut rr_cache = cache_algos::init(Algos::RR, 0, None);
+    assert!(!rr_cache.access(&1));
+    assert!(!rr_cache.access(&2));
+    assert!(rr_cache.access(&2));
+}
@@ -0,0 +1,84 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lru::LRUCache;
+use cache_algos::s3fifo::S3FifoCache;
+
+#[test]
+fn requested_element_goes_to_main_queue() {
+    let mut s3fifo_cache = S3FifoCache::new(10, None);
+    for i in 1..11 {
+        assert_eq!(s3fifo_cache.insert(i), None);
+    }
+    s3fifo_cache.touch(&1);
+    s3fifo_cache.touch(&1);
+    // `1` is moved to the main queue, `2` is discarded and remembered in the ghost queue.
+    assert_eq!(s3fifo_cache.insert(11), Some(2));
+    assert!(s3fifo_cache.contains(&1));
+    // `2` is found in the ghost queue and put into the main queue.
+    assert_eq!(s3fifo_cache.insert(2), Some(3));
+    for i in 12..20 {
+        s3fifo_cache.insert(i);
+    }
+    assert!(s3fifo_cache.contains(&1));
+    assert!(s3fifo_cache.contains(&2));
+    assert!(!s3fifo_cache.contains(&4));
+    assert!(s3fifo_cache.remove(&2));
+    assert!(!s3fifo_cache.remove(&2));
+    assert_eq!(s3fifo_cache.len(), 9);
+}
+
+#[test]
+fn one_hit_elements_are_filtered() {
+    // Every hot element is requested three times, then they are mixed with elements, which are
+    // requested only once.
+    let mut ram = vec![(0..5).chain(0..5).chain(0..5).collect::<Vec<_>>()];
+    for i in 0..10 {
+        ram.push(vec![100 + 2 * i, 101 + 2 * i, i % 5]);
+    }
+
+    assert_eq!(LRUCache::new(6, None).run(&ram), (10, 35));
+    assert_eq!(S3FifoCache::new(6, None).with_small(0.2).run(&ram), (20, 25));
+}
+
+#[test]
+#[should_panic]
+fn wrong_small_ratio() {
+    S3FifoCache::<u64>::new(10, None).with_small(1.5);
+}
+
+#[test]
+fn not_requested_element_leaves_small_queue() {
+    let mut s3fifo_cache = S3FifoCache::new(10, None);
+    for i in 1..11 {
+        s3fifo_cache.insert(i);
+    }
+    // `1` was not requested in the small queue, so it is discarded, not moved to the main queue.
+    assert_eq!(s3fifo_cache.insert(11), Some(1));
+}
+
+#[test]
+fn any_small_ratio() {
+    let ram = common::random_ram(18);
+    for size in &common::SIZES {
+        for ratio in &[0.0, 0.1, 0.5, 1.0] {
+            let mut s3fifo_cache = S3FifoCache::new(*size, None).with_small(*ratio);
+            common::check_policy(&mut s3fifo_cache, &ram, |_| ());
+        }
+    }
+}
+
+#[test]
+fn twice_requested_element_goes_to_main_queue() {
+    let mut s3fifo_cache = S3FifoCache::new(10, None);
+    for i in 1..11 {
+        s3fifo_cache.insert(i);
+    }
+    // `1` was put and hit once, so it is moved to the main queue and `2` is discarded.
+    s3fifo_cache.touch(&1);
+    assert_eq!(s3fifo_cache.insert(11), Some(2));
+    assert!(s3fifo_cache.contains(&1));
+}
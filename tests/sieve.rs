@@ -0,0 +1,44 @@
+extern crate cache_algos;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::fifo::FifoCache;
+use cache_algos::sieve::SieveCache;
+
+#[test]
+fn visited_element_is_retained() {
+    let ram = vec![vec![1, 2, 3, 1, 4, 5, 1]];
+
+    assert_eq!(FifoCache::new(3, None).run(&ram), (1, 6));
+    assert_eq!(SieveCache::new(3, None).run(&ram), (2, 5));
+}
+
+#[test]
+fn hand_keeps_position() {
+    let mut sieve_cache = SieveCache::new(3, None);
+    assert_eq!(sieve_cache.insert(1), None);
+    assert_eq!(sieve_cache.insert(2), None);
+    assert_eq!(sieve_cache.insert(3), None);
+    sieve_cache.touch(&1);
+    // The hand clears bit of `1` and stops at `2`.
+    assert_eq!(sieve_cache.insert(4), Some(2));
+    assert_eq!(sieve_cache.insert(5), Some(3));
+    sieve_cache.touch(&4);
+    // `1` is behind the hand, so new element `5` is discarded before it.
+    assert_eq!(sieve_cache.insert(6), Some(5));
+    assert!(sieve_cache.contains(&1));
+    assert!(sieve_cache.remove(&1));
+    assert!(!sieve_cache.remove(&1));
+    assert_eq!(sieve_cache.insert(7), None);
+    assert_eq!(sieve_cache.len(), 3);
+}
+
+#[test]
+fn one_hit_elements_are_discarded_first() {
+    // `1` and `2` are visited, the scan of `3..8` is discarded by the hand.
+    let ram = vec![vec![1, 2, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2]];
+
+    assert_eq!(FifoCache::new(4, None).run(&ram), (2, 10));
+    assert_eq!(SieveCache::new(4, None).run(&ram), (4, 8));
+}
+
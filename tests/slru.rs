@@ -0,0 +1,81 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lru::LRUCache;
+use cache_algos::slru::SLRUCache;
+
+#[test]
+fn sizes_of_segments() {
+    let slru_cache = SLRUCache::<i32>::new(10, None);
+    assert_eq!(slru_cache.segments(), 2);
+    assert_eq!(slru_cache.segment_size(0), 5);
+    assert_eq!(slru_cache.segment_size(1), 5);
+
+    let slru_cache = SLRUCache::<i32>::new(10, None).with_segments(4).with_protected(0.8);
+    assert_eq!(slru_cache.segments(), 4);
+    let sizes = (0..4).map(|i| slru_cache.segment_size(i)).collect::<Vec<_>>();
+    assert_eq!(sizes, vec![2, 3, 3, 2]);
+}
+
+#[test]
+fn scan_resistance() {
+    // `1` and `2` are protected, so the scan discards only probationary lines.
+    let ram = vec![vec![1, 2, 1, 2], vec![3, 4, 5, 6], vec![1, 2]];
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (2, 8));
+    assert_eq!(SLRUCache::new(4, None).run(&ram), (4, 6));
+}
+
+#[test]
+fn promotion_and_demotion() {
+    let mut slru_cache = SLRUCache::new(4, None).with_segments(4).with_protected(0.75);
+    slru_cache.insert(1);
+    for segment in 1..4 {
+        slru_cache.touch(&1);
+        assert_eq!(slru_cache.segment_len(segment), 1);
+    }
+    // The highest segment keeps the line.
+    slru_cache.touch(&1);
+    assert_eq!(slru_cache.segment_len(3), 1);
+
+    slru_cache.insert(2);
+    slru_cache.touch(&2);
+    assert_eq!(slru_cache.segment_len(1), 1);
+    // `3` takes place of `2` in the second segment, `2` goes back to the probationary segment.
+    slru_cache.insert(3);
+    assert_eq!(slru_cache.touch(&3), None);
+    assert_eq!(slru_cache.segment_len(0), 1);
+    assert_eq!(slru_cache.segment_len(1), 1);
+    slru_cache.insert(4);
+    assert_eq!(slru_cache.victim(), Some(2));
+    assert_eq!(slru_cache.insert(5), Some(2));
+    assert!(slru_cache.remove(&1));
+    assert!(!slru_cache.remove(&1));
+    assert_eq!(slru_cache.len(), 3);
+}
+
+#[test]
+fn invariants() {
+    let ram = common::random_ram(23);
+    for size in &common::SIZES {
+        for segments in 2..6 {
+            for protected in &[0.0, 0.25, 0.5, 0.8, 1.0] {
+                let mut slru_cache = SLRUCache::new(*size, None)
+                    .with_segments(segments)
+                    .with_protected(*protected);
+                let total = (0..segments).map(|i| slru_cache.segment_size(i)).sum::<usize>();
+                assert_eq!(total, *size);
+                common::check_size(&mut slru_cache, &ram, |cache| {
+                    let len = (0..segments).map(|i| cache.segment_len(i)).sum::<usize>();
+                    assert_eq!(len, cache.len());
+                    for i in 1..segments {
+                        assert!(cache.segment_len(i) <= cache.segment_size(i));
+                    }
+                });
+            }
+        }
+    }
+}
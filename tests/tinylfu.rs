@@ -0,0 +1,109 @@
+extern crate cache_algos;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::{Cache, Policy};
+use cache_algos::lru::LRUCache;
+use cache_algos::sieve::SieveCache;
+use cache_algos::tinylfu::{TinyLFU, TinyLFUCache, WTinyLFUCache};
+
+#[test]
+fn sketch_counts_requests() {
+    let mut filter = TinyLFU::new(100);
+    assert_eq!(filter.frequency(&1), 0);
+    filter.increment(&1);
+    assert_eq!(filter.frequency(&1), 1);
+    filter.increment(&1);
+    assert_eq!(filter.frequency(&1), 2);
+    for _ in 0..20 {
+        filter.increment(&1);
+    }
+    // 4 bits counter and the doorkeeper bit.
+    assert_eq!(filter.frequency(&1), 16);
+    assert!(filter.admit(&1, &2));
+    assert!(!filter.admit(&2, &1));
+    filter.clear();
+    assert_eq!(filter.frequency(&1), 0);
+}
+
+#[test]
+fn sketch_ages() {
+    let mut filter = TinyLFU::with_sample(100, 10);
+    for _ in 0..9 {
+        filter.increment(&1);
+    }
+    assert_eq!(filter.frequency(&1), 9);
+    // The first request is recorded by the doorkeeper, which is cleared, 9 requests are halved.
+    filter.increment(&1);
+    assert_eq!(filter.frequency(&1), 4);
+}
+
+#[test]
+fn rare_element_is_rejected() {
+    let mut cache = Cache::new(Box::new(TinyLFUCache::new(2, None)));
+    cache.put(1, "one");
+    cache.put(2, "two");
+    for _ in 0..3 {
+        cache.get(&1);
+        cache.get(&2);
+    }
+    assert_eq!(cache.put(3, "three"), Some((3, "three")));
+    assert!(cache.contains(&1));
+    assert!(cache.contains(&2));
+    assert!(!cache.contains(&3));
+    // Requests of `3` are counted, so it replaces the least recently used element.
+    for _ in 0..4 {
+        cache.put(3, "three");
+    }
+    assert!(cache.contains(&3));
+    assert!(!cache.contains(&1));
+}
+
+#[test]
+fn rejection_keeps_state() {
+    // The same policy without the filter gets the same requests except the rejected element.
+    let mut tinylfu_cache = TinyLFUCache::with_policy(Box::new(SieveCache::new(2, None)), None);
+    let mut sieve_cache = SieveCache::new(2, None);
+    for key in &[1, 2] {
+        tinylfu_cache.insert(*key);
+        sieve_cache.insert(*key);
+    }
+    tinylfu_cache.touch(&1);
+    sieve_cache.touch(&1);
+    tinylfu_cache.touch(&2);
+    sieve_cache.touch(&2);
+    assert_eq!(tinylfu_cache.insert(3), Some(3));
+    assert!(tinylfu_cache.contains(&1));
+    assert!(tinylfu_cache.contains(&2));
+    assert_eq!(tinylfu_cache.len(), 2);
+    // `4` is admitted after several requests, both caches discard the same element.
+    let mut evicted = Some(4);
+    while evicted == Some(4) {
+        evicted = tinylfu_cache.insert(4);
+    }
+    assert!(tinylfu_cache.contains(&4));
+    assert_eq!(evicted, sieve_cache.insert(4));
+}
+
+#[test]
+fn scan_resistance() {
+    // Hot elements are mixed with the scan of elements, which are requested only once.
+    let ram = (0..20)
+        .map(|i| (0..5).chain(1000 * (i + 1)..1000 * (i + 1) + 10).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    assert_eq!(LRUCache::new(10, None).run(&ram), (0, 300));
+    assert_eq!(TinyLFUCache::new(10, None).run(&ram), (95, 205));
+    assert_eq!(WTinyLFUCache::new(10, None).run(&ram), (95, 205));
+}
+
+#[test]
+fn window_admits_new_element() {
+    // Hot elements fill the cache, then new element is requested twice.
+    let mut ram = vec![(0..10).chain(0..10).chain(0..10).collect::<Vec<_>>()];
+    ram.push(vec![100, 100]);
+
+    // TinyLFU rejects `100` twice, W-TinyLFU keeps it in the window.
+    assert_eq!(TinyLFUCache::new(10, None).run(&ram), (20, 12));
+    assert_eq!(WTinyLFUCache::new(10, None).with_window(0.2).run(&ram), (21, 11));
+}
+
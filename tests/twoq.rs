@@ -0,0 +1,62 @@
+extern crate cache_algos;
+
+mod common;
+
+use cache_algos::CacheAlgorithm;
+use cache_algos::cache::Policy;
+use cache_algos::lru::LRUCache;
+use cache_algos::twoq::TwoQCache;
+
+#[test]
+fn hot_element_survives_scan() {
+    // `1` is discarded from A1in by `5`, then it is requested again and put into Am, so the scan
+    // of `6..10` discards only elements of A1in.
+    let ram = vec![vec![1, 2, 3, 4, 5, 1, 1, 6, 7, 8, 9, 10, 1]];
+
+    assert_eq!(LRUCache::new(4, None).run(&ram), (1, 12));
+    assert_eq!(TwoQCache::new(4, None).run(&ram), (2, 11));
+}
+
+#[test]
+fn without_a1out_nothing_is_promoted() {
+    let ram = vec![vec![1, 2, 3, 4, 5, 1, 1, 6, 7, 8, 9, 10, 1]];
+
+    // Every element is kept only in A1in, so the cache works as FIFO.
+    assert_eq!(TwoQCache::new(4, None).with_kout(0.0).run(&ram), (1, 12));
+}
+
+#[test]
+fn evicted_elements() {
+    let mut twoq_cache = TwoQCache::new(4, None);
+    for key in 1..5 {
+        assert_eq!(twoq_cache.insert(key), None);
+    }
+    assert_eq!(twoq_cache.insert(5), Some(1));
+    assert!(!twoq_cache.contains(&1));
+    // `1` is in A1out and goes to Am, A1in is still greater than Kin.
+    assert_eq!(twoq_cache.insert(1), Some(2));
+    assert!(twoq_cache.remove(&1));
+    assert!(!twoq_cache.remove(&2));
+    assert_eq!(twoq_cache.len(), 3);
+}
+
+#[test]
+fn a1in_hits_do_not_promote() {
+    let mut twoq_cache = TwoQCache::new(4, None);
+    // Correlated requests of `1` hit A1in, but don't move it.
+    for elem in &[1, 1, 1, 2, 3, 4] {
+        twoq_cache.access(elem);
+    }
+    assert_eq!(twoq_cache.insert(5), Some(1));
+}
+
+#[test]
+fn any_kin_and_kout() {
+    let ram = common::random_ram(11);
+    for &(kin, kout) in &[(0.25, 0.5), (0.0, 0.5), (1.0, 1.0), (0.5, 2.0)] {
+        for size in &common::SIZES {
+            let mut twoq_cache = TwoQCache::new(*size, None).with_kin(kin).with_kout(kout);
+            common::check_policy(&mut twoq_cache, &ram, |_| ());
+        }
+    }
+}